@@ -0,0 +1,58 @@
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::{hint::black_box, time::Instant};
+
+use day1::{par_radix_sort, LocationId};
+
+use common::XorShift;
+
+// Compares the comparison sort part1 has always used with the parallel radix sort.
+// Run with `cargo bench --bench sort`.
+
+fn bench<T: LocationId>(name: &str, ids: Vec<T>) {
+    const ROUNDS: u32 = 5;
+
+    let time = |sort: &dyn Fn(&mut Vec<T>)| {
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            let mut ids = ids.clone();
+            sort(&mut ids);
+            black_box(ids);
+        }
+        start.elapsed() / ROUNDS
+    };
+
+    let comparison = time(&|ids| ids.sort());
+    let radix = time(&|ids| par_radix_sort(ids));
+
+    let mut expected = ids.clone();
+    expected.sort();
+    let mut actual = ids;
+    par_radix_sort(&mut actual);
+    assert_eq!(
+        expected, actual,
+        "{name}: radix sort gave a different order"
+    );
+
+    println!("{name:<24} sort: {comparison:>12.2?}   par_radix_sort: {radix:>12.2?}");
+}
+
+fn main() {
+    let mut rng = XorShift(0x2024_0010);
+
+    for len in [10_000, 1_000_000, 10_000_000] {
+        bench(
+            &format!("i32 x {len}"),
+            (0..len).map(|_| rng.next() as i32).collect(),
+        );
+        bench(
+            &format!("i64 x {len}"),
+            (0..len).map(|_| rng.next() as i64).collect(),
+        );
+        bench(
+            &format!("puzzle-like x {len}"),
+            (0..len).map(|_| (rng.next() % 100_000) as i32).collect(),
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use anyhow::{anyhow, Result};
+
+use crate::{parse::parse_rows, LocationLists, ParseMode};
+
+/// The cost of pairing a left location ID with a right one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CostFunction {
+    /// `|left - right|`, the puzzle's own distance.
+    Absolute,
+    /// `(left - right)²`
+    Squared,
+    /// `|left - right|`, but never more than the cap.
+    Capped(i128),
+    /// Costs looked up by pair, falling back to `|left - right|` for pairs not in the table.
+    Table(HashMap<(i64, i64), i128>),
+}
+
+impl CostFunction {
+    /// Reads a cost table with one `left right cost` triple per line.
+    pub fn parse_table(input: &str) -> Result<Self> {
+        // Read everything as i128 so one parse covers the costs too, then narrow the IDs back down.
+        let (rows, _) = parse_rows::<i128>(input, 3, ParseMode::Strict)?;
+
+        let table = rows
+            .into_iter()
+            .map(|row| {
+                let left = row[0].try_into()?;
+                let right = row[1].try_into()?;
+                Ok(((left, right), row[2]))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self::Table(table))
+    }
+
+    pub fn cost(&self, left: i64, right: i64) -> Option<i128> {
+        let distance = (right as i128 - left as i128).abs();
+
+        match self {
+            Self::Absolute => Some(distance),
+            Self::Squared => distance.checked_mul(distance),
+            Self::Capped(cap) => Some(distance.min(*cap)),
+            Self::Table(table) => Some(table.get(&(left, right)).copied().unwrap_or(distance)),
+        }
+    }
+
+    /// Whether pairing the sorted lists in order is already optimal.
+    ///
+    /// This holds for any convex function of the difference between the IDs.
+    pub fn sorted_is_optimal(&self) -> bool {
+        matches!(self, Self::Absolute | Self::Squared)
+    }
+}
+
+impl FromStr for CostFunction {
+    type Err = anyhow::Error;
+
+    /// Accepts `abs`, `squared`, `capped:N` or `table:PATH`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(":") {
+            None if s == "abs" => Ok(Self::Absolute),
+            None if s == "squared" => Ok(Self::Squared),
+            Some(("capped", cap)) => Ok(Self::Capped(cap.parse()?)),
+            Some(("table", path)) => Ok(Self::parse_table(&std::fs::read_to_string(path)?)?),
+            _ => Err(anyhow!(
+                "Unknown cost `{s}`, expected abs, squared, capped:N or table:PATH"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignmentMethod {
+    /// Pair the sorted lists in order, only valid when [`CostFunction::sorted_is_optimal`].
+    Sorted,
+    Hungarian,
+}
+
+impl fmt::Display for AssignmentMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sorted => write!(f, "sorted"),
+            Self::Hungarian => write!(f, "hungarian"),
+        }
+    }
+}
+
+/// A minimum cost pairing of the left and right lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// `(left index, right index)` pairs, indexing into the unsorted lists.
+    pub pairs: Vec<(usize, usize)>,
+    pub total_cost: i128,
+    pub method: AssignmentMethod,
+}
+
+impl LocationLists<i64> {
+    /// Finds a minimum cost pairing, using the sorted pairing whenever it's known to be optimal.
+    pub fn assign(&self, cost: &CostFunction) -> Result<Assignment> {
+        if cost.sorted_is_optimal() {
+            self.assign_sorted(cost)
+        } else {
+            self.assign_hungarian(cost)
+        }
+    }
+
+    pub fn assign_sorted(&self, cost: &CostFunction) -> Result<Assignment> {
+        let mut left: Vec<usize> = (0..self.left.len()).collect();
+        let mut right: Vec<usize> = (0..self.right.len()).collect();
+
+        left.sort_by_key(|&i| self.left[i]);
+        right.sort_by_key(|&j| self.right[j]);
+
+        let pairs: Vec<(usize, usize)> = left.into_iter().zip(right).collect();
+
+        Ok(Assignment {
+            total_cost: self.total_cost(&pairs, cost)?,
+            pairs,
+            method: AssignmentMethod::Sorted,
+        })
+    }
+
+    /// Solves the assignment problem for any cost function with the Hungarian algorithm, in O(n³).
+    pub fn assign_hungarian(&self, cost: &CostFunction) -> Result<Assignment> {
+        let n = self.left.len();
+        if n != self.right.len() {
+            return Err(anyhow!("The left and right lists have different lengths"));
+        }
+
+        // Costs are kept well below i128::MAX so the potentials can't overflow while they're adjusted.
+        let limit = i128::MAX / 4 / (n as i128 + 1);
+        let pair_cost = |i: usize, j: usize| {
+            cost.cost(self.left[i], self.right[j])
+                .filter(|cost| cost.abs() <= limit)
+                .ok_or(anyhow!("Pairing cost too large for {n} rows"))
+        };
+
+        // 1-indexed, with row/column 0 used as the sentinel the augmenting paths start from.
+        let mut u = vec![0i128; n + 1];
+        let mut v = vec![0i128; n + 1];
+        let mut matched_row = vec![0usize; n + 1];
+        let mut way = vec![0usize; n + 1];
+
+        for row in 1..=n {
+            matched_row[0] = row;
+            let mut column = 0;
+            let mut min_slack = vec![i128::MAX; n + 1];
+            let mut used = vec![false; n + 1];
+
+            while matched_row[column] != 0 {
+                used[column] = true;
+                let current_row = matched_row[column];
+                let mut delta = i128::MAX;
+                let mut next_column = 0;
+
+                for j in 1..=n {
+                    if !used[j] {
+                        let slack = pair_cost(current_row - 1, j - 1)? - u[current_row] - v[j];
+                        if slack < min_slack[j] {
+                            min_slack[j] = slack;
+                            way[j] = column;
+                        }
+                        if min_slack[j] < delta {
+                            delta = min_slack[j];
+                            next_column = j;
+                        }
+                    }
+                }
+
+                for j in 0..=n {
+                    if used[j] {
+                        u[matched_row[j]] += delta;
+                        v[j] -= delta;
+                    } else {
+                        min_slack[j] -= delta;
+                    }
+                }
+
+                column = next_column;
+            }
+
+            while column != 0 {
+                let previous = way[column];
+                matched_row[column] = matched_row[previous];
+                column = previous;
+            }
+        }
+
+        let mut pairs: Vec<(usize, usize)> = (1..=n).map(|j| (matched_row[j] - 1, j - 1)).collect();
+        pairs.sort();
+
+        Ok(Assignment {
+            total_cost: self.total_cost(&pairs, cost)?,
+            pairs,
+            method: AssignmentMethod::Hungarian,
+        })
+    }
+
+    fn total_cost(&self, pairs: &[(usize, usize)], cost: &CostFunction) -> Result<i128> {
+        pairs
+            .iter()
+            .try_fold(0i128, |acc, &(i, j)| {
+                acc.checked_add(cost.cost(self.left[i], self.right[j])?)
+            })
+            .ok_or(anyhow!("Overflow while computing the total pairing cost"))
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fs::File, io::Read};
+
+use anyhow::{anyhow, Result};
+use day1::{report_skipped, CostFunction, IntKind, LocationLists, Options};
+
+// Usage: assign [--cost abs|squared|capped:N|table:PATH] [--hungarian] [--check] [--pairs]
+// `--hungarian` skips the sorted fast path, `--check` solves both ways and compares the costs.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    if options.int.is_some_and(|kind| kind != IntKind::I64) {
+        return Err(anyhow!("assign only supports i64 location IDs"));
+    }
+
+    let mut cost = CostFunction::Absolute;
+    let mut hungarian = false;
+    let mut check = false;
+    let mut show_pairs = false;
+
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cost" => cost = args.next().ok_or(anyhow!("--cost needs a cost"))?.parse()?,
+            "--hungarian" => hungarian = true,
+            "--check" => check = true,
+            "--pairs" => show_pairs = true,
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let mut input = File::open("./input.txt")?;
+
+    let mut input_str = String::new();
+    input.read_to_string(&mut input_str)?;
+
+    let (lists, skipped) = LocationLists::<i64>::parse_with_mode(&input_str, options.mode)?;
+    report_skipped(&skipped);
+
+    let assignment = if hungarian {
+        lists.assign_hungarian(&cost)?
+    } else {
+        lists.assign(&cost)?
+    };
+
+    if check {
+        let sorted = lists.assign_sorted(&cost)?;
+        let hungarian = lists.assign_hungarian(&cost)?;
+
+        println!("Sorted: {}", sorted.total_cost);
+        println!("Hungarian: {}", hungarian.total_cost);
+
+        if cost.sorted_is_optimal() && sorted.total_cost != hungarian.total_cost {
+            return Err(anyhow!("Sorted and Hungarian pairings disagree"));
+        }
+    }
+
+    if show_pairs {
+        assignment.pairs.iter().for_each(|&(i, j)| {
+            println!("{} {}", lists.left[i], lists.right[j]);
+        });
+    }
+
+    println!(
+        "Total cost ({}): {}",
+        assignment.method, assignment.total_cost
+    );
+
+    Ok(())
+}
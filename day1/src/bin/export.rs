@@ -0,0 +1,71 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+};
+
+use anyhow::{anyhow, Result};
+use day1::{
+    dispatch_int, report_skipped, write_pairings, ExportFormat, LocationId, LocationLists, Options,
+};
+
+fn run<T: LocationId>(
+    input: &str,
+    output_path: Option<&str>,
+    format: ExportFormat,
+    options: &Options,
+) -> Result<()> {
+    let (lists, skipped) = LocationLists::<T>::parse_with_mode(input, options.mode)?;
+    report_skipped(&skipped);
+    let pairings = lists.pairings()?;
+
+    // Only created once the input has been read, so a bad input leaves an existing file alone.
+    let output: Box<dyn Write> = match output_path {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut output = BufWriter::new(output);
+    write_pairings(&mut output, &pairings, format)?;
+    output.flush()?;
+
+    Ok(())
+}
+
+// Usage: export [--format csv|json] [--output PATH]
+// Writes every pair matched for part1 to stdout, or to the output path if one is given.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+
+    let mut format = ExportFormat::default();
+    let mut output_path = None;
+
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = args
+                    .next()
+                    .ok_or(anyhow!("--format needs csv or json"))?
+                    .parse()
+                    .map_err(|e: String| anyhow!(e))?
+            }
+            "--output" => {
+                output_path = Some(
+                    args.next()
+                        .ok_or(anyhow!("--output needs a path"))?
+                        .as_str(),
+                )
+            }
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let mut input = File::open("./input.txt")?;
+
+    let mut input_str = String::new();
+    input.read_to_string(&mut input_str)?;
+
+    dispatch_int!(
+        options.int.unwrap_or_default(),
+        run(&input_str, output_path, format, &options)
+    )
+}
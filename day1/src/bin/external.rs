@@ -0,0 +1,63 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Result};
+use day1::{dispatch_int, report_skipped, ExternalSort, LocationId, Options};
+
+fn run<T: LocationId>(
+    input: Box<dyn BufRead>,
+    sort: &ExternalSort,
+    options: &Options,
+) -> Result<()> {
+    let totals = sort.totals::<T>(input, options.mode)?;
+    report_skipped(&totals.skipped);
+
+    println!("Total distance: {}", totals.total_distance);
+    println!("Similarity score: {}", totals.similarity_score);
+
+    Ok(())
+}
+
+// Usage: external [--run-size N] [--fan-in N] [--temp-dir DIR] [PATH]
+// Reads from stdin when no path (or `-`) is given.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+
+    let mut sort = ExternalSort::default();
+    let mut path = None;
+
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--run-size" => {
+                sort.run_size = args
+                    .next()
+                    .ok_or(anyhow!("--run-size needs a number"))?
+                    .parse()?
+            }
+            "--fan-in" => {
+                sort.max_fan_in = args
+                    .next()
+                    .ok_or(anyhow!("--fan-in needs a number"))?
+                    .parse()?
+            }
+            "--temp-dir" => {
+                sort.temp_dir = Some(PathBuf::from(
+                    args.next().ok_or(anyhow!("--temp-dir needs a path"))?,
+                ))
+            }
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let input: Box<dyn BufRead> = match path.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+
+    dispatch_int!(options.int.unwrap_or_default(), run(input, &sort, &options))
+}
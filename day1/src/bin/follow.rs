@@ -0,0 +1,63 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use day1::{dispatch_int, parse_line, IncrementalLists, LocationId, Options, ParseMode};
+
+fn run<T: LocationId>(mut input: Box<dyn BufRead>, follow: bool, options: &Options) -> Result<()> {
+    let mut lists = IncrementalLists::<T>::new();
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        // A line without its newline yet is still being written, so keep it for the next read.
+        if input.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+            if follow {
+                thread::sleep(Duration::from_millis(200));
+                continue;
+            } else if line.is_empty() {
+                return Ok(());
+            }
+        }
+
+        line_number += 1;
+        match parse_line::<T>(line_number, &line) {
+            Ok(Some((left, right))) => {
+                lists.push(left, right)?;
+                println!(
+                    "Pairs: {}, total distance: {}, similarity score: {}",
+                    lists.len(),
+                    lists.total_distance(),
+                    lists.similarity_score()
+                );
+            }
+            Ok(None) => {}
+            Err(error) if options.mode == ParseMode::Lenient => eprintln!("Skipped {error}"),
+            Err(error) => return Err(error.into()),
+        }
+
+        line.clear();
+    }
+}
+
+// Usage: follow [PATH]
+// Prints the updated answers after every line of stdin. Given a path, keeps watching the file
+// for new lines like `tail -f` instead of stopping at the end.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+
+    let (input, follow): (Box<dyn BufRead>, bool) = match options.extra.as_slice() {
+        [] => (Box::new(io::stdin().lock()), false),
+        [path] => (Box::new(BufReader::new(File::open(path)?)), true),
+        [_, arg, ..] => return Err(anyhow!("Unexpected argument `{arg}`")),
+    };
+
+    dispatch_int!(
+        options.int.unwrap_or_default(),
+        run(input, follow, &options)
+    )
+}
@@ -0,0 +1,79 @@
+use std::{fmt::Display, fs::File, io::Read};
+
+use anyhow::{anyhow, Result};
+use day1::{dispatch_int, report_skipped, LocationId, LocationTable, Options};
+
+fn print_matrix<T: Display>(title: &str, matrix: &[Vec<T>]) {
+    let cells: Vec<Vec<String>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+    let width = cells
+        .iter()
+        .flatten()
+        .map(|cell| cell.len())
+        .chain([matrix.len().to_string().len()])
+        .max()
+        .unwrap_or(0);
+
+    println!("{title}");
+    print!("{:>width$}", "");
+    (0..matrix.len()).for_each(|column| print!(" {:>width$}", column + 1));
+    println!();
+
+    cells.iter().enumerate().for_each(|(row, cells)| {
+        print!("{:>width$}", row + 1);
+        cells.iter().for_each(|cell| print!(" {cell:>width$}"));
+        println!();
+    });
+}
+
+fn run<T: LocationId>(input: &str, columns: Option<usize>, options: &Options) -> Result<()> {
+    let (table, skipped) = match columns {
+        Some(columns) => LocationTable::<T>::parse_with_columns(input, columns, options.mode)?,
+        None => LocationTable::<T>::parse_with_mode(input, options.mode)?,
+    };
+    report_skipped(&skipped);
+
+    let matrix = table.pairwise()?;
+
+    print_matrix("Total distance", &matrix.total_distance);
+    println!();
+    print_matrix(
+        "Similarity score (row scored against column)",
+        &matrix.similarity_score,
+    );
+
+    Ok(())
+}
+
+// Usage: matrix [--columns N]
+// Without `--columns`, the number of columns is the most common row width.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+
+    let mut columns = None;
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--columns" => {
+                columns = Some(
+                    args.next()
+                        .ok_or(anyhow!("--columns needs a number"))?
+                        .parse()?,
+                )
+            }
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let mut input = File::open("./input.txt")?;
+
+    let mut input_str = String::new();
+    input.read_to_string(&mut input_str)?;
+
+    dispatch_int!(
+        options.int.unwrap_or_default(),
+        run(&input_str, columns, &options)
+    )
+}
@@ -1,43 +1,37 @@
 use std::{fs::File, io::Read};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use day1::{dispatch_int, report_skipped, LocationId, LocationLists, Options, SortMethod};
 
-fn main() -> Result<()> {
-    let mut input = File::open("./input.txt")?;
+fn run<T: LocationId>(input: &str, sort: SortMethod, options: &Options) -> Result<()> {
+    let (lists, skipped) = LocationLists::<T>::parse_with_mode(input, options.mode)?;
+    report_skipped(&skipped);
 
-    let mut input_str = String::new();
-    input.read_to_string(&mut input_str)?;
+    let result = lists.total_distance_with(sort)?;
 
-    let data: Vec<(i32, i32)> = input_str
-        .split("\n")
-        .filter_map(|line| {
-            let mut split_line = line.split_whitespace();
-
-            Some((
-                split_line.next()?.parse().ok()?,
-                split_line.next()?.parse().ok()?,
-            ))
-        })
-        .collect();
+    println!("{result}");
 
-    let mut left = Vec::new();
-    let mut right = Vec::new();
+    Ok(())
+}
 
-    data.iter().for_each(|datum| {
-        left.push(datum.0);
-        right.push(datum.1);
-    });
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
 
-    left.sort();
-    right.sort();
+    let mut sort = SortMethod::Comparison;
+    for arg in &options.extra {
+        match arg.as_str() {
+            "--radix" => sort = SortMethod::ParallelRadix,
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
 
-    let result: i32 = left
-        .iter()
-        .zip(right)
-        .map(|(left, right)| (right - left).abs())
-        .sum();
+    let mut input = File::open("./input.txt")?;
 
-    println!("{result}");
+    let mut input_str = String::new();
+    input.read_to_string(&mut input_str)?;
 
-    Ok(())
+    dispatch_int!(
+        options.int.unwrap_or_default(),
+        run(&input_str, sort, &options)
+    )
 }
@@ -1,44 +1,27 @@
 use std::{fs::File, io::Read};
 
 use anyhow::Result;
+use day1::{dispatch_int, report_skipped, LocationId, LocationLists, Options};
+
+fn run<T: LocationId>(input: &str, options: &Options) -> Result<()> {
+    let (lists, skipped) = LocationLists::<T>::parse_with_mode(input, options.mode)?;
+    report_skipped(&skipped);
+
+    let result = lists.similarity_score()?;
+
+    println!("{result}");
+
+    Ok(())
+}
 
 fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_extra()?;
+
     let mut input = File::open("./input.txt")?;
 
     let mut input_str = String::new();
     input.read_to_string(&mut input_str)?;
 
-    let data: Vec<(i32, i32)> = input_str
-        .split("\n")
-        .filter_map(|line| {
-            let mut split_line = line.split_whitespace();
-
-            Some((
-                split_line.next()?.parse().ok()?,
-                split_line.next()?.parse().ok()?,
-            ))
-        })
-        .collect();
-
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-
-    data.iter().for_each(|datum| {
-        left.push(datum.0);
-        right.push(datum.1);
-    });
-
-    let result: i32 = left
-        .iter()
-        .map(|num| {
-            let occurances = right
-                .iter()
-                .fold(0, |acc, x| if x == num { acc + 1 } else { acc });
-            occurances * num
-        })
-        .sum();
-
-    println!("{result}");
-
-    Ok(())
+    dispatch_int!(options.int.unwrap_or_default(), run(&input_str, &options))
 }
@@ -0,0 +1,61 @@
+use std::{fs::File, io::Read};
+
+use anyhow::Result;
+use day1::{dispatch_int, report_skipped, LocationId, LocationLists, Options};
+
+fn run<T: LocationId>(input: &str, options: &Options) -> Result<()> {
+    let (lists, skipped) = LocationLists::<T>::parse_with_mode(input, options.mode)?;
+    report_skipped(&skipped);
+
+    println!("Total distance: {}", lists.total_distance()?);
+    println!("Similarity score: {}", lists.similarity_score()?);
+
+    let reconciliation = lists.reconcile();
+    if reconciliation.is_empty() {
+        println!("\nBoth lists hold the same IDs the same number of times.");
+        return Ok(());
+    }
+
+    println!(
+        "\nOnly in the left list ({}):",
+        reconciliation.left_only.len()
+    );
+    reconciliation
+        .left_only
+        .iter()
+        .for_each(|(id, count)| println!("  {id} x{count}"));
+
+    println!(
+        "\nOnly in the right list ({}):",
+        reconciliation.right_only.len()
+    );
+    reconciliation
+        .right_only
+        .iter()
+        .for_each(|(id, count)| println!("  {id} x{count}"));
+
+    println!(
+        "\nIn both lists with different counts ({}):",
+        reconciliation.count_mismatches.len()
+    );
+    reconciliation.count_mismatches.iter().for_each(|mismatch| {
+        println!(
+            "  {} left x{}, right x{}",
+            mismatch.id, mismatch.left, mismatch.right
+        )
+    });
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_extra()?;
+
+    let mut input = File::open("./input.txt")?;
+
+    let mut input_str = String::new();
+    input.read_to_string(&mut input_str)?;
+
+    dispatch_int!(options.int.unwrap_or_default(), run(&input_str, &options))
+}
@@ -0,0 +1,110 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use serde::{ser::Error, Serialize, Serializer};
+use serde_json::value::RawValue;
+
+use crate::{occurrences, LocationId, LocationLists, OverflowError, TOTAL_DISTANCE};
+
+/// One left ID matched with a right ID after both lists are sorted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(bound(serialize = "T: fmt::Display"))]
+pub struct Pairing<T> {
+    /// Position of the pair in the sorted lists, starting at 0.
+    pub rank: usize,
+    #[serde(serialize_with = "as_number")]
+    pub left: T,
+    #[serde(serialize_with = "as_number")]
+    pub right: T,
+    #[serde(serialize_with = "as_number")]
+    pub distance: T,
+    /// How many times `left` appears in the right list.
+    pub right_occurrences: usize,
+}
+
+/// Writes an ID as a JSON number of however many digits it has, where a big integer's own
+/// serialization would be a list of its digits.
+fn as_number<T: fmt::Display, S: Serializer>(id: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    RawValue::from_string(id.to_string())
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
+
+impl<T: LocationId> LocationLists<T> {
+    /// Every pair matched by [`LocationLists::total_distance`], in sorted order.
+    pub fn pairings(&self) -> Result<Vec<Pairing<T>>, OverflowError> {
+        let mut left = self.left.clone();
+        let mut right = self.right.clone();
+
+        left.sort();
+        right.sort();
+
+        let occurrences = occurrences(&right);
+
+        left.iter()
+            .zip(&right)
+            .enumerate()
+            .map(|(rank, (left, right))| {
+                Ok(Pairing {
+                    rank,
+                    left: left.clone(),
+                    right: right.clone(),
+                    distance: left.distance(right).ok_or(TOTAL_DISTANCE)?,
+                    right_occurrences: occurrences.get(left).copied().unwrap_or(0),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown format `{s}`, expected csv or json")),
+        }
+    }
+}
+
+/// Writes pairings as CSV with a header row, or as a JSON array of objects.
+///
+/// IDs are written as plain numbers in both formats, so big integers keep their full precision.
+pub fn write_pairings<T: fmt::Display>(
+    mut writer: impl Write,
+    pairings: &[Pairing<T>],
+    format: ExportFormat,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            writeln!(writer, "rank,left,right,distance,right_occurrences")?;
+            pairings.iter().try_for_each(|pairing| {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{}",
+                    pairing.rank,
+                    pairing.left,
+                    pairing.right,
+                    pairing.distance,
+                    pairing.right_occurrences
+                )
+            })
+        }
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, pairings)?;
+            writeln!(writer)
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Lines, Seek, Write},
+    iter::Peekable,
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    parse::parse_row, LineError, LocationId, OverflowError, ParseError, ParseMode,
+    SIMILARITY_SCORE, TOTAL_DISTANCE,
+};
+
+/// Computes the day1 answers for lists too big to hold in memory.
+///
+/// Each column is read in runs of `run_size` IDs, which are sorted and spilled to temporary
+/// files, then the runs are merged back together in sorted order.
+///
+/// No more than `max_fan_in` runs are merged at once. Once that many runs of the same size
+/// have built up, they're merged into one bigger run, so the number of files held open grows
+/// with the logarithm of the input size rather than linearly.
+#[derive(Debug, Clone)]
+pub struct ExternalSort {
+    pub run_size: usize,
+    pub max_fan_in: usize,
+    /// Where to put the runs, defaulting to the system temporary directory.
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl Default for ExternalSort {
+    fn default() -> Self {
+        Self {
+            run_size: 1_000_000,
+            max_fan_in: 64,
+            temp_dir: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalTotals<T> {
+    pub total_distance: T,
+    pub similarity_score: T,
+    pub skipped: Vec<LineError>,
+}
+
+impl ExternalSort {
+    pub fn totals<T: LocationId>(
+        &self,
+        reader: impl BufRead,
+        mode: ParseMode,
+    ) -> Result<ExternalTotals<T>> {
+        let run_size = self.run_size.max(1);
+
+        let mut left = Runs::default();
+        let mut right = Runs::default();
+        let mut left_buffer = Vec::with_capacity(run_size);
+        let mut right_buffer = Vec::with_capacity(run_size);
+        let mut skipped = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            match parse_row::<T>(index + 1, &line?, 2) {
+                Ok(Some(row)) => {
+                    let mut row = row.into_iter();
+                    left_buffer.extend(row.next());
+                    right_buffer.extend(row.next());
+                }
+                Ok(None) => {}
+                Err(error) => skipped.push(error),
+            }
+
+            if left_buffer.len() >= run_size {
+                left.spill(&mut left_buffer, self)?;
+                right.spill(&mut right_buffer, self)?;
+            }
+        }
+
+        if mode == ParseMode::Strict && !skipped.is_empty() {
+            return Err(ParseError { errors: skipped }.into());
+        }
+
+        if !left_buffer.is_empty() {
+            left.spill(&mut left_buffer, self)?;
+            right.spill(&mut right_buffer, self)?;
+        }
+
+        let total_distance = left.merged(self)?.zip(right.merged(self)?).try_fold(
+            T::zero(),
+            |acc, (left, right): (Result<T>, Result<T>)| {
+                let distance = left?.distance(&right?).ok_or(TOTAL_DISTANCE)?;
+                Ok::<_, anyhow::Error>(acc.checked_add(&distance).ok_or(TOTAL_DISTANCE)?)
+            },
+        )?;
+
+        let similarity_score = similarity_score_merged(left.merged(self)?, right.merged(self)?)?;
+
+        Ok(ExternalTotals {
+            total_distance,
+            similarity_score,
+            skipped,
+        })
+    }
+}
+
+/// Sorted runs of one column, each in its own temporary file.
+#[derive(Default)]
+struct Runs {
+    /// Each run with its level: how many rounds of merging went into it.
+    runs: Vec<(usize, File)>,
+}
+
+impl Runs {
+    fn spill<T: LocationId>(&mut self, buffer: &mut Vec<T>, sort: &ExternalSort) -> Result<()> {
+        buffer.sort_unstable();
+        let file = write_run(buffer.drain(..).map(Ok), sort)?;
+        self.runs.push((0, file));
+
+        // Like carrying in a counter: `max_fan_in` runs on one level become a single run on the
+        // next. Levels only ever decrease along `runs`, so those runs are always at the end.
+        let fan_in = sort.max_fan_in.max(2);
+        while self.runs.len() >= fan_in {
+            let group = &self.runs[self.runs.len() - fan_in..];
+            let level = group[0].0;
+            if group.iter().any(|(other, _)| *other != level) {
+                break;
+            }
+
+            self.merge_last::<T>(fan_in, level + 1, sort)?;
+        }
+
+        Ok(())
+    }
+
+    /// Merges the last `count` runs into one run at `level`.
+    fn merge_last<T: LocationId>(
+        &mut self,
+        count: usize,
+        level: usize,
+        sort: &ExternalSort,
+    ) -> Result<()> {
+        let group = self.runs.split_off(self.runs.len() - count);
+        let file = write_run(merge_files::<T>(group.iter().map(|(_, file)| file))?, sort)?;
+        self.runs.push((level, file));
+
+        Ok(())
+    }
+
+    /// Reads every run back from the start, merged into one sorted stream, after first merging
+    /// the smallest runs together until there are no more than `max_fan_in` left.
+    fn merged<T: LocationId>(&mut self, sort: &ExternalSort) -> Result<MergedRuns<'_, T>> {
+        let fan_in = sort.max_fan_in.max(2);
+        while self.runs.len() > fan_in {
+            let level = self.runs[self.runs.len() - fan_in].0;
+            self.merge_last::<T>(fan_in, level + 1, sort)?;
+        }
+
+        merge_files(self.runs.iter().map(|(_, file)| file))
+    }
+}
+
+/// Writes sorted IDs to a new temporary file, one per line.
+fn write_run<T: LocationId>(
+    ids: impl Iterator<Item = Result<T>>,
+    sort: &ExternalSort,
+) -> Result<File> {
+    let file = match &sort.temp_dir {
+        Some(dir) => tempfile::tempfile_in(dir)?,
+        None => tempfile::tempfile()?,
+    };
+
+    let mut writer = BufWriter::new(file);
+    for id in ids {
+        writeln!(writer, "{}", id?)?;
+    }
+
+    Ok(writer.into_inner()?)
+}
+
+/// Reads the runs back from the start, merged into one sorted stream.
+fn merge_files<'a, T: LocationId>(
+    files: impl ExactSizeIterator<Item = &'a File>,
+) -> Result<MergedRuns<'a, T>> {
+    let mut merged = MergedRuns {
+        runs: Vec::with_capacity(files.len()),
+        heap: BinaryHeap::with_capacity(files.len()),
+    };
+
+    for (run, mut file) in files.enumerate() {
+        file.rewind()?;
+        merged.runs.push(BufReader::new(file).lines());
+        merged.push_next(run)?;
+    }
+
+    Ok(merged)
+}
+
+struct MergedRuns<'a, T> {
+    runs: Vec<Lines<BufReader<&'a File>>>,
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+}
+
+impl<T: LocationId> MergedRuns<'_, T> {
+    fn push_next(&mut self, run: usize) -> Result<()> {
+        if let Some(line) = self.runs[run].next() {
+            let id = line?
+                .parse()
+                .map_err(|_| anyhow!("Couldn't read back a spilled run"))?;
+            self.heap.push(Reverse((id, run)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: LocationId> Iterator for MergedRuns<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, run)) = self.heap.pop()?;
+
+        Some(self.push_next(run).map(|_| id))
+    }
+}
+
+/// Similarity score of two sorted streams, counting each run of equal IDs on both sides.
+fn similarity_score_merged<T: LocationId>(
+    left: impl Iterator<Item = Result<T>>,
+    right: impl Iterator<Item = Result<T>>,
+) -> Result<T> {
+    let mut left = left.peekable();
+    let mut right = right.peekable();
+    let mut score = T::zero();
+
+    while let Some(id) = left.next().transpose()? {
+        let left_count = 1 + count_equal(&mut left, &id)?;
+
+        while right
+            .next_if(|other| matches!(other, Ok(other) if *other < id))
+            .is_some()
+        {}
+        let right_count = count_equal(&mut right, &id)?;
+
+        let contribution = left_count
+            .checked_mul(right_count)
+            .and_then(|count| id.checked_mul_count(count))
+            .ok_or(SIMILARITY_SCORE)?;
+        score = score
+            .checked_add(&contribution)
+            .ok_or::<OverflowError>(SIMILARITY_SCORE)?;
+    }
+
+    Ok(score)
+}
+
+/// Consumes and counts the IDs equal to `id` at the front of a sorted stream.
+fn count_equal<T: LocationId>(
+    stream: &mut Peekable<impl Iterator<Item = Result<T>>>,
+    id: &T,
+) -> Result<usize> {
+    let mut count = 0;
+
+    while let Some(next) = stream.next_if(|next| !matches!(next, Ok(next) if next != id)) {
+        next?;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,192 @@
+use std::{fmt, hash::Hash, str::FromStr};
+
+use num_bigint::BigInt;
+
+/// An integer type location IDs can be stored as.
+///
+/// Every operation is checked, returning `None` rather than wrapping or panicking on overflow.
+pub trait LocationId:
+    Clone + Ord + Hash + FromStr + fmt::Display + fmt::Debug + Send + Sync
+{
+    fn zero() -> Self;
+
+    /// The absolute difference between two IDs.
+    fn distance(&self, other: &Self) -> Option<Self>;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    /// This ID multiplied by a number of occurrences.
+    fn checked_mul_count(&self, count: usize) -> Option<Self>;
+
+    /// A key whose unsigned order matches the order of the IDs, for types narrow enough to
+    /// radix sort.
+    fn radix_key(&self) -> Option<u64> {
+        None
+    }
+
+    /// The ID a [`LocationId::radix_key`] was made from.
+    fn from_radix_key(_key: u64) -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_signed_location_id {
+    ($($int:ty => $radix_key:expr, $from_radix_key:expr);*) => {
+        $(
+            impl LocationId for $int {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn distance(&self, other: &Self) -> Option<Self> {
+                    <$int>::checked_sub(*other, *self)?.checked_abs()
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$int>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$int>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul_count(&self, count: usize) -> Option<Self> {
+                    <$int>::checked_mul(*self, count.try_into().ok()?)
+                }
+
+                fn radix_key(&self) -> Option<u64> {
+                    $radix_key(*self)
+                }
+
+                fn from_radix_key(key: u64) -> Option<Self> {
+                    $from_radix_key(key)
+                }
+            }
+        )*
+    };
+}
+
+// Flipping the sign bit puts negative IDs before positive ones when compared as unsigned.
+impl_signed_location_id!(
+    i32 => |id: i32| Some(((id as u32) ^ (1 << 31)) as u64),
+        |key: u64| Some((key as u32 ^ (1 << 31)) as i32);
+    i64 => |id: i64| Some((id as u64) ^ (1 << 63)),
+        |key: u64| Some((key ^ (1 << 63)) as i64);
+    i128 => |_| None, |_| None
+);
+
+impl LocationId for u64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn distance(&self, other: &Self) -> Option<Self> {
+        Some(self.abs_diff(*other))
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        u64::checked_sub(*self, *other)
+    }
+
+    fn checked_mul_count(&self, count: usize) -> Option<Self> {
+        u64::checked_mul(*self, count.try_into().ok()?)
+    }
+
+    fn radix_key(&self) -> Option<u64> {
+        Some(*self)
+    }
+
+    fn from_radix_key(key: u64) -> Option<Self> {
+        Some(key)
+    }
+}
+
+impl LocationId for BigInt {
+    fn zero() -> Self {
+        BigInt::ZERO
+    }
+
+    fn distance(&self, other: &Self) -> Option<Self> {
+        Some((other - self).magnitude().clone().into())
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_mul_count(&self, count: usize) -> Option<Self> {
+        Some(self * count)
+    }
+}
+
+/// The integer types location IDs can be read as, chosen at runtime with `--int`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntKind {
+    #[default]
+    I32,
+    I64,
+    U64,
+    I128,
+    Big,
+}
+
+impl FromStr for IntKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "i32" => Ok(Self::I32),
+            "i64" => Ok(Self::I64),
+            "u64" => Ok(Self::U64),
+            "i128" => Ok(Self::I128),
+            "big" => Ok(Self::Big),
+            _ => Err(format!(
+                "Unknown integer type `{s}`, expected one of i32, i64, u64, i128 or big"
+            )),
+        }
+    }
+}
+
+/// Calls a generic function with the [`LocationId`] type matching an [`IntKind`].
+///
+/// `dispatch_int!(kind, run(a, b))` expands to `run::<i32>(a, b)`, `run::<i64>(a, b)` and so on.
+#[macro_export]
+macro_rules! dispatch_int {
+    ($kind:expr, $run:ident ( $($arg:expr),* $(,)? )) => {
+        match $kind {
+            $crate::IntKind::I32 => $run::<i32>($($arg),*),
+            $crate::IntKind::I64 => $run::<i64>($($arg),*),
+            $crate::IntKind::U64 => $run::<u64>($($arg),*),
+            $crate::IntKind::I128 => $run::<i128>($($arg),*),
+            $crate::IntKind::Big => $run::<$crate::BigInt>($($arg),*),
+        }
+    };
+}
+
+/// Returned when a total no longer fits in the chosen [`LocationId`] type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    pub computing: &'static str,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Overflow while computing the {}, try a wider --int type",
+            self.computing
+        )
+    }
+}
+
+impl std::error::Error for OverflowError {}
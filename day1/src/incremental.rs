@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use crate::{LocationId, OverflowError, SIMILARITY_SCORE, TOTAL_DISTANCE};
+
+/// Location lists that keep their total distance and similarity score up to date as pairs are
+/// added.
+///
+/// Each [`push`](Self::push) is O(n): inserting into the sorted columns shifts everything after
+/// the new ID. That's no better asymptotically than re-summing the sorted columns, but it saves
+/// re-sorting and rebuilding the occurrence counts after every pair.
+#[derive(Debug, Clone)]
+pub struct IncrementalLists<T> {
+    /// Both columns are kept sorted.
+    left: Vec<T>,
+    right: Vec<T>,
+    left_occurrences: HashMap<T, usize>,
+    right_occurrences: HashMap<T, usize>,
+    total_distance: T,
+    similarity_score: T,
+}
+
+impl<T: LocationId> Default for IncrementalLists<T> {
+    fn default() -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+            left_occurrences: HashMap::new(),
+            right_occurrences: HashMap::new(),
+            total_distance: T::zero(),
+            similarity_score: T::zero(),
+        }
+    }
+}
+
+impl<T: LocationId> IncrementalLists<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.left.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    pub fn total_distance(&self) -> &T {
+        &self.total_distance
+    }
+
+    pub fn similarity_score(&self) -> &T {
+        &self.similarity_score
+    }
+
+    /// Adds a pair, leaving the lists untouched if either answer would overflow.
+    ///
+    /// Inserting `left` at rank `p` and `right` at rank `q` only changes which IDs are paired
+    /// between those two ranks, so only that stretch of the total distance is recomputed. The
+    /// inserts themselves still move every ID after `p` and `q`.
+    pub fn push(&mut self, left: T, right: T) -> Result<(), OverflowError> {
+        let p = self.left.partition_point(|id| *id <= left);
+        let q = self.right.partition_point(|id| *id <= right);
+        let (low, high) = (p.min(q), p.max(q));
+
+        let removed = (low..high).try_fold(T::zero(), |acc, rank| {
+            acc.checked_add(&self.left[rank].distance(&self.right[rank])?)
+        });
+
+        let inserted_at = |column: &[T], rank: usize, at: usize, id: &T| match rank.cmp(&at) {
+            std::cmp::Ordering::Less => column[rank].clone(),
+            std::cmp::Ordering::Equal => id.clone(),
+            std::cmp::Ordering::Greater => column[rank - 1].clone(),
+        };
+        let added = (low..=high).try_fold(T::zero(), |acc, rank| {
+            let left = inserted_at(&self.left, rank, p, &left);
+            let right = inserted_at(&self.right, rank, q, &right);
+            acc.checked_add(&left.distance(&right)?)
+        });
+
+        let total_distance = removed
+            .zip(added)
+            .and_then(|(removed, added)| {
+                self.total_distance
+                    .checked_sub(&removed)?
+                    .checked_add(&added)
+            })
+            .ok_or(TOTAL_DISTANCE)?;
+
+        // Each new ID scores against everything already in the other column, and the new left
+        // ID counts towards the new right one.
+        let left_score = left.checked_mul_count(occurrences(&self.right_occurrences, &left));
+        let right_count = occurrences(&self.left_occurrences, &right) + usize::from(left == right);
+        let right_score = right.checked_mul_count(right_count);
+
+        let similarity_score = left_score
+            .zip(right_score)
+            .and_then(|(left_score, right_score)| {
+                self.similarity_score
+                    .checked_add(&left_score)?
+                    .checked_add(&right_score)
+            })
+            .ok_or(SIMILARITY_SCORE)?;
+
+        self.total_distance = total_distance;
+        self.similarity_score = similarity_score;
+
+        *self.left_occurrences.entry(left.clone()).or_insert(0) += 1;
+        *self.right_occurrences.entry(right.clone()).or_insert(0) += 1;
+        self.left.insert(p, left);
+        self.right.insert(q, right);
+
+        Ok(())
+    }
+}
+
+fn occurrences<T: LocationId>(occurrences: &HashMap<T, usize>, id: &T) -> usize {
+    occurrences.get(id).copied().unwrap_or(0)
+}
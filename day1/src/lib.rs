@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+pub use assignment::{Assignment, AssignmentMethod, CostFunction};
+pub use export::{write_pairings, ExportFormat, Pairing};
+pub use external::{ExternalSort, ExternalTotals};
+pub use id::{IntKind, LocationId, OverflowError};
+pub use incremental::IncrementalLists;
+pub use num_bigint::BigInt;
+pub use options::{report_skipped, Options};
+use parse::parse_rows;
+pub use parse::{LineError, LineErrorKind, ParseError, ParseMode};
+pub use radix::{par_radix_sort, SortMethod};
+pub use reconcile::{CountMismatch, Reconciliation};
+pub use table::{LocationTable, PairwiseMatrix};
+
+mod assignment;
+mod export;
+mod external;
+mod id;
+mod incremental;
+mod options;
+mod parse;
+mod radix;
+mod reconcile;
+mod table;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationLists<T = i32> {
+    pub left: Vec<T>,
+    pub right: Vec<T>,
+}
+
+impl<T> Default for LocationLists<T> {
+    fn default() -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+}
+
+impl<T: LocationId> LocationLists<T> {
+    /// Reads one pair of location IDs per line, failing if any row is malformed.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with_mode(input, ParseMode::Strict).map(|(lists, _)| lists)
+    }
+
+    /// Reads one pair of location IDs per line, also returning the rows skipped in
+    /// [`ParseMode::Lenient`].
+    pub fn parse_with_mode(
+        input: &str,
+        mode: ParseMode,
+    ) -> Result<(Self, Vec<LineError>), ParseError> {
+        let (rows, skipped) = parse_rows(input, 2, mode)?;
+
+        let mut lists = Self::default();
+        rows.into_iter().for_each(|row| {
+            let mut row = row.into_iter();
+            lists.left.extend(row.next());
+            lists.right.extend(row.next());
+        });
+
+        Ok((lists, skipped))
+    }
+
+    /// Sum of the distances between the smallest left and right IDs, then the next smallest, and so on.
+    pub fn total_distance(&self) -> Result<T, OverflowError> {
+        self.total_distance_with(SortMethod::Comparison)
+    }
+
+    pub fn total_distance_with(&self, sort: SortMethod) -> Result<T, OverflowError> {
+        let mut left = self.left.clone();
+        let mut right = self.right.clone();
+
+        sort.sort(&mut left);
+        sort.sort(&mut right);
+
+        total_distance_sorted(&left, &right)
+    }
+
+    pub fn similarity_score(&self) -> Result<T, OverflowError> {
+        similarity_score(&self.left, &self.right)
+    }
+}
+
+/// Reads a single row of a location list, or `None` if the line is blank.
+pub fn parse_line<T: LocationId>(
+    line_number: usize,
+    line: &str,
+) -> Result<Option<(T, T)>, LineError> {
+    Ok(parse::parse_row(line_number, line, 2)?.map(|row| {
+        let mut row = row.into_iter();
+        (row.next().unwrap(), row.next().unwrap())
+    }))
+}
+
+pub(crate) const TOTAL_DISTANCE: OverflowError = OverflowError {
+    computing: "total distance",
+};
+pub(crate) const SIMILARITY_SCORE: OverflowError = OverflowError {
+    computing: "similarity score",
+};
+
+/// Total distance between two lists that are already sorted.
+pub fn total_distance_sorted<T: LocationId>(left: &[T], right: &[T]) -> Result<T, OverflowError> {
+    left.iter()
+        .zip(right)
+        .try_fold(T::zero(), |acc, (left, right)| {
+            acc.checked_add(&left.distance(right)?)
+        })
+        .ok_or(TOTAL_DISTANCE)
+}
+
+/// Similarity score found by scanning the whole of `right` for every element of `left`.
+///
+/// This is O(n²) and only kept around to check [`similarity_score`] against.
+pub fn similarity_score_naive<T: LocationId>(left: &[T], right: &[T]) -> Result<T, OverflowError> {
+    left.iter()
+        .try_fold(T::zero(), |acc, num| {
+            let occurances = right
+                .iter()
+                .fold(0, |acc, x| if x == num { acc + 1 } else { acc });
+            acc.checked_add(&num.checked_mul_count(occurances)?)
+        })
+        .ok_or(SIMILARITY_SCORE)
+}
+
+/// How many times each ID appears in a list.
+pub(crate) fn occurrences<T: LocationId>(list: &[T]) -> HashMap<&T, usize> {
+    let mut occurrences = HashMap::new();
+    list.iter()
+        .for_each(|num| *occurrences.entry(num).or_insert(0) += 1);
+    occurrences
+}
+
+/// Similarity score found from a frequency table of `right`, in linear time.
+pub fn similarity_score<T: LocationId>(left: &[T], right: &[T]) -> Result<T, OverflowError> {
+    let occurrences = occurrences(right);
+
+    left.iter()
+        .try_fold(T::zero(), |acc, num| {
+            let occurrences = occurrences.get(num).copied().unwrap_or(0);
+            acc.checked_add(&num.checked_mul_count(occurrences)?)
+        })
+        .ok_or(SIMILARITY_SCORE)
+}
@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+
+use crate::{IntKind, LineError, ParseMode};
+
+/// Command line flags shared by the day1 binaries.
+///
+/// Flags a binary handles itself are left in `extra`, in the order they were given.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub mode: ParseMode,
+    /// `None` unless `--int` was given, so binaries can tell an explicit choice from the default.
+    pub int: Option<IntKind>,
+    pub extra: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            mode: ParseMode::Strict,
+            int: None,
+            extra: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    pub fn from_env() -> Result<Self> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--lenient" => options.mode = ParseMode::Lenient,
+                "--int" => {
+                    options.int = Some(
+                        args.next()
+                            .ok_or(anyhow!("--int needs a type"))?
+                            .parse()
+                            .map_err(|e: String| anyhow!(e))?,
+                    )
+                }
+                _ => options.extra.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+impl Options {
+    /// Fails if any flag was left over for a binary that takes no flags of its own.
+    pub fn expect_no_extra(&self) -> Result<()> {
+        match self.extra.first() {
+            Some(arg) => Err(anyhow!("Unexpected argument `{arg}`")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Lists the rows skipped by a lenient parse on stderr.
+pub fn report_skipped(skipped: &[LineError]) {
+    if !skipped.is_empty() {
+        eprintln!("Skipped {} malformed line(s):", skipped.len());
+        skipped.iter().for_each(|error| eprintln!("  {error}"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Reject the whole input if any row is malformed, reporting every one.
+    Strict,
+    /// Skip malformed rows, keeping a record of each one.
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineErrorKind {
+    InvalidNumber,
+    MissingColumn,
+    ExtraColumn,
+}
+
+/// A malformed row. `line` and `column` are 1-based, with `column` counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub kind: LineErrorKind,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            LineErrorKind::InvalidNumber => write!(
+                f,
+                "line {}, column {}: `{}` is not a valid location ID",
+                self.line, self.column, self.token
+            ),
+            LineErrorKind::MissingColumn => write!(
+                f,
+                "line {}, column {}: expected another location ID",
+                self.line, self.column
+            ),
+            LineErrorKind::ExtraColumn => write!(
+                f,
+                "line {}, column {}: unexpected extra column `{}`",
+                self.line, self.column, self.token
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// Every malformed row found while parsing in [`ParseMode::Strict`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub errors: Vec<LineError>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} malformed row(s)", self.errors.len())?;
+        self.errors
+            .iter()
+            .try_for_each(|error| write!(f, "\n  {error}"))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits a line on whitespace, pairing each token with its 1-based character column.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    let mut start = None;
+    let mut chars = line.char_indices().peekable();
+
+    std::iter::from_fn(move || loop {
+        match chars.next() {
+            Some((index, char)) => {
+                column += 1;
+                if char.is_whitespace() {
+                    if let Some((start_column, start_index)) = start.take() {
+                        return Some((start_column, &line[start_index..index]));
+                    }
+                } else if start.is_none() {
+                    start = Some((column, index));
+                }
+            }
+            None => {
+                return start
+                    .take()
+                    .map(|(start_column, start_index)| (start_column, &line[start_index..]))
+            }
+        }
+    })
+}
+
+/// Parses exactly `columns` location IDs from a line, or `None` if the line is blank.
+pub(crate) fn parse_row<T: FromStr>(
+    line_number: usize,
+    line: &str,
+    columns: usize,
+) -> Result<Option<Vec<T>>, LineError> {
+    let mut row = Vec::with_capacity(columns);
+
+    for (column, token) in tokens(line) {
+        if row.len() == columns {
+            return Err(LineError {
+                line: line_number,
+                column,
+                token: token.to_string(),
+                kind: LineErrorKind::ExtraColumn,
+            });
+        }
+
+        row.push(token.parse().map_err(|_| LineError {
+            line: line_number,
+            column,
+            token: token.to_string(),
+            kind: LineErrorKind::InvalidNumber,
+        })?);
+    }
+
+    if row.is_empty() {
+        Ok(None)
+    } else if row.len() < columns {
+        Err(LineError {
+            line: line_number,
+            column: line.trim_end().chars().count() + 1,
+            token: String::new(),
+            kind: LineErrorKind::MissingColumn,
+        })
+    } else {
+        Ok(Some(row))
+    }
+}
+
+/// Parses every non-blank line into a row of `columns` IDs, returning the rows and the skipped lines.
+pub(crate) fn parse_rows<T: FromStr>(
+    input: &str,
+    columns: usize,
+    mode: ParseMode,
+) -> Result<(Vec<Vec<T>>, Vec<LineError>), ParseError> {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    input
+        .lines()
+        .enumerate()
+        .for_each(|(index, line)| match parse_row(index + 1, line, columns) {
+            Ok(Some(row)) => rows.push(row),
+            Ok(None) => {}
+            Err(error) => errors.push(error),
+        });
+
+    match mode {
+        ParseMode::Strict if !errors.is_empty() => Err(ParseError { errors }),
+        _ => Ok((rows, errors)),
+    }
+}
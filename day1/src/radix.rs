@@ -0,0 +1,135 @@
+use rayon::prelude::*;
+
+use crate::LocationId;
+
+/// How the columns are sorted before they're paired up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortMethod {
+    /// The standard library's single-threaded comparison sort.
+    #[default]
+    Comparison,
+    /// [`par_radix_sort`], falling back to a parallel comparison sort for IDs without a radix key.
+    ParallelRadix,
+}
+
+impl SortMethod {
+    pub fn sort<T: LocationId>(self, ids: &mut Vec<T>) {
+        match self {
+            Self::Comparison => ids.sort(),
+            Self::ParallelRadix => par_radix_sort(ids),
+        }
+    }
+}
+
+/// Sorts IDs by their [`LocationId::radix_key`] using every rayon thread.
+///
+/// The keys are first split into buckets on the most significant byte any of them use, then
+/// each bucket is finished off independently with a least significant digit radix sort.
+pub fn par_radix_sort<T: LocationId>(ids: &mut Vec<T>) {
+    let Some(mut keys) = ids
+        .par_iter()
+        .map(|id| id.radix_key())
+        .collect::<Option<Vec<u64>>>()
+    else {
+        ids.par_sort();
+        return;
+    };
+
+    // Measuring keys from the smallest one skips the high bytes every key shares.
+    let min_key = keys.par_iter().copied().min().unwrap_or(0);
+    keys.par_iter_mut().for_each(|key| *key -= min_key);
+
+    let max_key = keys.par_iter().copied().max().unwrap_or(0);
+    let bytes = (u64::BITS - max_key.leading_zeros()).div_ceil(8) as usize;
+
+    if bytes > 0 {
+        let top_shift = (bytes - 1) * 8;
+
+        let counts = keys
+            .par_chunks(1 << 16)
+            .map(|chunk| byte_counts(chunk, top_shift))
+            .reduce(
+                || [0; 256],
+                |mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                    a
+                },
+            );
+
+        let mut buffer = vec![0; keys.len()];
+        scatter(&keys, &mut buffer, top_shift, &counts);
+
+        let mut buckets = Vec::with_capacity(256);
+        let mut rest = buffer.as_mut_slice();
+        for count in counts {
+            let (bucket, remaining) = rest.split_at_mut(count);
+            buckets.push(bucket);
+            rest = remaining;
+        }
+
+        buckets
+            .into_par_iter()
+            .for_each(|bucket| lsd_radix_sort(bucket, top_shift / 8));
+
+        keys = buffer;
+    }
+
+    ids.clear();
+    ids.par_extend(
+        keys.into_par_iter()
+            .map(|key| T::from_radix_key(key + min_key).expect("radix keys convert back to IDs")),
+    );
+}
+
+fn byte_counts(keys: &[u64], shift: usize) -> [usize; 256] {
+    let mut counts = [0; 256];
+    keys.iter()
+        .for_each(|key| counts[(key >> shift) as usize & 0xff] += 1);
+    counts
+}
+
+/// Stable counting sort of `source` into `target` on the byte at `shift`.
+fn scatter(source: &[u64], target: &mut [u64], shift: usize, counts: &[usize; 256]) {
+    let mut offsets = [0; 256];
+    (1..256).for_each(|byte| offsets[byte] = offsets[byte - 1] + counts[byte - 1]);
+
+    source.iter().for_each(|&key| {
+        let byte = (key >> shift) as usize & 0xff;
+        target[offsets[byte]] = key;
+        offsets[byte] += 1;
+    });
+}
+
+/// Sorts on the lowest `bytes` bytes of each key.
+fn lsd_radix_sort(keys: &mut [u64], bytes: usize) {
+    if keys.len() < 64 {
+        keys.sort_unstable();
+        return;
+    }
+
+    let mut buffer = vec![0; keys.len()];
+    let mut sorted_in_buffer = false;
+
+    for byte in 0..bytes {
+        let shift = byte * 8;
+        let (source, target) = if sorted_in_buffer {
+            (&buffer[..], &mut keys[..])
+        } else {
+            (&keys[..], &mut buffer[..])
+        };
+
+        let counts = byte_counts(source, shift);
+
+        // Every key shares this byte, so the pass wouldn't move anything.
+        if counts.contains(&source.len()) {
+            continue;
+        }
+
+        scatter(source, target, shift, &counts);
+        sorted_in_buffer = !sorted_in_buffer;
+    }
+
+    if sorted_in_buffer {
+        keys.copy_from_slice(&buffer);
+    }
+}
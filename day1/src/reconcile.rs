@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use crate::{LocationId, LocationLists};
+
+/// An ID found in both lists, but a different number of times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountMismatch<T> {
+    pub id: T,
+    pub left: usize,
+    pub right: usize,
+}
+
+/// Where the two lists disagree, each section sorted by ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reconciliation<T> {
+    /// IDs only in the left list, with how many times they appear.
+    pub left_only: Vec<(T, usize)>,
+    /// IDs only in the right list, with how many times they appear.
+    pub right_only: Vec<(T, usize)>,
+    pub count_mismatches: Vec<CountMismatch<T>>,
+}
+
+impl<T: LocationId> LocationLists<T> {
+    pub fn reconcile(&self) -> Reconciliation<T> {
+        let mut counts: BTreeMap<&T, (usize, usize)> = BTreeMap::new();
+        self.left
+            .iter()
+            .for_each(|id| counts.entry(id).or_default().0 += 1);
+        self.right
+            .iter()
+            .for_each(|id| counts.entry(id).or_default().1 += 1);
+
+        let mut reconciliation = Reconciliation {
+            left_only: Vec::new(),
+            right_only: Vec::new(),
+            count_mismatches: Vec::new(),
+        };
+
+        counts
+            .into_iter()
+            .for_each(|(id, (left, right))| match (left, right) {
+                (left, 0) => reconciliation.left_only.push((id.clone(), left)),
+                (0, right) => reconciliation.right_only.push((id.clone(), right)),
+                (left, right) if left != right => {
+                    reconciliation.count_mismatches.push(CountMismatch {
+                        id: id.clone(),
+                        left,
+                        right,
+                    })
+                }
+                _ => {}
+            });
+
+        reconciliation
+    }
+}
+
+impl<T> Reconciliation<T> {
+    pub fn is_empty(&self) -> bool {
+        self.left_only.is_empty() && self.right_only.is_empty() && self.count_mismatches.is_empty()
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    parse::parse_rows, similarity_score, total_distance_sorted, LineError, LocationId,
+    OverflowError, ParseError, ParseMode,
+};
+
+/// Any number of location lists, read one column per list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationTable<T = i32> {
+    pub columns: Vec<Vec<T>>,
+}
+
+/// Answers for every pair of columns, indexed `[first column][second column]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairwiseMatrix<T> {
+    pub total_distance: Vec<Vec<T>>,
+    pub similarity_score: Vec<Vec<T>>,
+}
+
+impl<T: LocationId> LocationTable<T> {
+    /// Reads one ID per column on each line, taking the number of columns from the most common
+    /// row width, so a malformed first row doesn't decide it. Ties go to the width seen first.
+    pub fn parse_with_mode(
+        input: &str,
+        mode: ParseMode,
+    ) -> Result<(Self, Vec<LineError>), ParseError> {
+        let mut widths: Vec<(usize, usize)> = Vec::new();
+        input
+            .lines()
+            .map(|line| line.split_whitespace().count())
+            .filter(|&count| count > 0)
+            .for_each(
+                |count| match widths.iter_mut().find(|(width, _)| *width == count) {
+                    Some((_, rows)) => *rows += 1,
+                    None => widths.push((count, 1)),
+                },
+            );
+
+        // `max_by_key` keeps the last of equal maxima, so search from the back.
+        let width = widths
+            .iter()
+            .rev()
+            .max_by_key(|&&(_, rows)| rows)
+            .map_or(0, |&(width, _)| width);
+
+        Self::parse_with_columns(input, width, mode)
+    }
+
+    /// Reads exactly `width` IDs per line.
+    pub fn parse_with_columns(
+        input: &str,
+        width: usize,
+        mode: ParseMode,
+    ) -> Result<(Self, Vec<LineError>), ParseError> {
+        let (rows, skipped) = parse_rows(input, width, mode)?;
+
+        let mut columns = vec![Vec::with_capacity(rows.len()); width];
+        rows.into_iter().for_each(|row| {
+            columns
+                .iter_mut()
+                .zip(row)
+                .for_each(|(column, id)| column.push(id))
+        });
+
+        Ok((Self { columns }, skipped))
+    }
+
+    pub fn pairwise(&self) -> Result<PairwiseMatrix<T>, OverflowError> {
+        let sorted: Vec<Vec<T>> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone();
+                column.sort();
+                column
+            })
+            .collect();
+
+        let total_distance = sorted
+            .iter()
+            .map(|first| {
+                sorted
+                    .iter()
+                    .map(|second| total_distance_sorted(first, second))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let similarity_score = self
+            .columns
+            .iter()
+            .map(|first| {
+                self.columns
+                    .iter()
+                    .map(|second| similarity_score(first, second))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(PairwiseMatrix {
+            total_distance,
+            similarity_score,
+        })
+    }
+}
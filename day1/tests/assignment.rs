@@ -0,0 +1,90 @@
+mod common;
+
+use std::collections::HashMap;
+
+use day1::{CostFunction, LocationLists};
+
+use common::XorShift;
+
+/// The cheapest total cost over every way of pairing the lists, by trying them all.
+fn brute_force(lists: &LocationLists<i64>, cost: &CostFunction) -> i128 {
+    fn search(
+        lists: &LocationLists<i64>,
+        cost: &CostFunction,
+        row: usize,
+        used: &mut Vec<bool>,
+    ) -> i128 {
+        if row == lists.left.len() {
+            return 0;
+        }
+
+        let mut best = None;
+        for j in 0..lists.right.len() {
+            if !used[j] {
+                used[j] = true;
+                let total = cost.cost(lists.left[row], lists.right[j]).unwrap()
+                    + search(lists, cost, row + 1, used);
+                used[j] = false;
+                best = Some(best.map_or(total, |best: i128| best.min(total)));
+            }
+        }
+        best.unwrap()
+    }
+
+    search(lists, cost, 0, &mut vec![false; lists.right.len()])
+}
+
+fn random_table(rng: &mut XorShift, max: u64) -> CostFunction {
+    let table: HashMap<(i64, i64), i128> = (0..20)
+        .map(|_| {
+            let pair = (rng.below(max) as i64, rng.below(max) as i64);
+            (pair, rng.below(100) as i128)
+        })
+        .collect();
+    CostFunction::Table(table)
+}
+
+#[test]
+fn hungarian_matches_brute_force() {
+    let mut rng = XorShift(0x2024_0007);
+
+    for _ in 0..300 {
+        let n = rng.below(7) as usize;
+        let max = 1 + rng.below(20);
+        let lists = LocationLists {
+            left: (0..n).map(|_| rng.below(max) as i64 - 5).collect(),
+            right: (0..n).map(|_| rng.below(max) as i64 - 5).collect(),
+        };
+
+        let costs = [
+            CostFunction::Absolute,
+            CostFunction::Squared,
+            CostFunction::Capped(rng.below(10) as i128),
+            random_table(&mut rng, max),
+        ];
+
+        for cost in &costs {
+            let hungarian = lists.assign_hungarian(cost).unwrap();
+            assert_eq!(
+                hungarian.total_cost,
+                brute_force(&lists, cost),
+                "{lists:?} under {cost:?}"
+            );
+
+            let mut left: Vec<usize> = hungarian.pairs.iter().map(|&(i, _)| i).collect();
+            let mut right: Vec<usize> = hungarian.pairs.iter().map(|&(_, j)| j).collect();
+            left.sort();
+            right.sort();
+            assert_eq!(left, (0..n).collect::<Vec<_>>());
+            assert_eq!(right, (0..n).collect::<Vec<_>>());
+
+            if cost.sorted_is_optimal() {
+                assert_eq!(
+                    lists.assign_sorted(cost).unwrap().total_cost,
+                    hungarian.total_cost,
+                    "{lists:?} under {cost:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use day1::{write_pairings, ExportFormat, LocationLists};
+use num_bigint::BigInt;
+use serde_json::{json, Value};
+
+fn export(format: ExportFormat) -> String {
+    let lists: LocationLists =
+        LocationLists::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+
+    let mut output = Vec::new();
+    write_pairings(&mut output, &lists.pairings().unwrap(), format).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn example_as_csv() {
+    assert_eq!(
+        export(ExportFormat::Csv),
+        "rank,left,right,distance,right_occurrences\n\
+         0,1,3,2,0\n\
+         1,2,3,1,0\n\
+         2,3,3,0,3\n\
+         3,3,4,1,3\n\
+         4,3,5,2,3\n\
+         5,4,9,5,1\n"
+    );
+}
+
+#[test]
+fn example_as_json() {
+    let output: Value = serde_json::from_str(&export(ExportFormat::Json)).unwrap();
+    let row = |rank, left, right, distance, right_occurrences| {
+        json!({
+            "rank": rank,
+            "left": left,
+            "right": right,
+            "distance": distance,
+            "right_occurrences": right_occurrences,
+        })
+    };
+
+    assert_eq!(
+        output,
+        json!([
+            row(0, 1, 3, 2, 0),
+            row(1, 2, 3, 1, 0),
+            row(2, 3, 3, 0, 3),
+            row(3, 3, 4, 1, 3),
+            row(4, 3, 5, 2, 3),
+            row(5, 4, 9, 5, 1),
+        ])
+    );
+}
+
+#[test]
+fn big_ids_are_plain_numbers() {
+    let lists: LocationLists<BigInt> =
+        LocationLists::parse("123456789012345678901234567890   1\n").unwrap();
+
+    let mut output = Vec::new();
+    write_pairings(&mut output, &lists.pairings().unwrap(), ExportFormat::Json).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("\"left\": 123456789012345678901234567890,"));
+    assert!(output.contains("\"distance\": 123456789012345678901234567889,"));
+}
+
+#[test]
+fn empty_lists() {
+    let mut output = Vec::new();
+    write_pairings::<i32>(&mut output, &[], ExportFormat::Json).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "[]\n");
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use day1::{ExternalSort, LocationLists, ParseMode};
+
+use common::XorShift;
+
+/// Random rows with plenty of repeated IDs, and the odd malformed row if `bad_rows` is set.
+fn random_input(rng: &mut XorShift, rows: usize, bad_rows: bool) -> String {
+    let mut input = String::new();
+    for _ in 0..rows {
+        match rng.below(20) {
+            0 if bad_rows => input.push_str("12 x3\n"),
+            1 if bad_rows => input.push_str("7\n"),
+            2 => input.push('\n'),
+            _ => input.push_str(&format!("{}   {}\n", rng.below(50), rng.below(50))),
+        }
+    }
+    input
+}
+
+#[test]
+fn matches_in_memory_answers() {
+    let mut rng = XorShift(0x2024_0005);
+
+    for (mode, bad_rows) in [
+        (ParseMode::Strict, false),
+        (ParseMode::Lenient, false),
+        (ParseMode::Lenient, true),
+    ] {
+        let input = random_input(&mut rng, 500, bad_rows);
+        let (lists, skipped) = LocationLists::<i32>::parse_with_mode(&input, mode).unwrap();
+
+        for run_size in [1, 2, 3, 7, 64, 1_000] {
+            for max_fan_in in [2, 3, 64] {
+                let sort = ExternalSort {
+                    run_size,
+                    max_fan_in,
+                    temp_dir: None,
+                };
+                let totals = sort.totals::<i32>(input.as_bytes(), mode).unwrap();
+
+                let context = format!("{mode:?}, run size {run_size}, fan-in {max_fan_in}");
+                assert_eq!(
+                    totals.total_distance,
+                    lists.total_distance().unwrap(),
+                    "{context}"
+                );
+                assert_eq!(
+                    totals.similarity_score,
+                    lists.similarity_score().unwrap(),
+                    "{context}"
+                );
+                assert_eq!(totals.skipped, skipped, "{context}");
+            }
+        }
+    }
+}
+
+#[test]
+fn strict_mode_fails_on_bad_rows() {
+    let sort = ExternalSort {
+        run_size: 2,
+        ..ExternalSort::default()
+    };
+
+    assert!(sort
+        .totals::<i32>("3 4\n4 x3\n2 5\n".as_bytes(), ParseMode::Strict)
+        .is_err());
+}
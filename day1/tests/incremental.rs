@@ -0,0 +1,48 @@
+mod common;
+
+use day1::{IncrementalLists, LocationLists};
+
+use common::XorShift;
+
+#[test]
+fn matches_recomputing_after_every_push() {
+    let mut rng = XorShift(0x2024_0011);
+
+    // A small range of IDs makes repeats, and so ties in the sorted order, common.
+    for max in [3, 50, 1_000_000] {
+        let mut incremental = IncrementalLists::<i64>::new();
+        let mut lists = LocationLists::<i64>::default();
+
+        for _ in 0..300 {
+            let left = rng.below(max) as i64 - max as i64 / 2;
+            let right = rng.below(max) as i64 - max as i64 / 2;
+
+            incremental.push(left, right).unwrap();
+            lists.left.push(left);
+            lists.right.push(right);
+
+            assert_eq!(incremental.len(), lists.left.len());
+            assert_eq!(
+                *incremental.total_distance(),
+                lists.total_distance().unwrap(),
+                "{lists:?}"
+            );
+            assert_eq!(
+                *incremental.similarity_score(),
+                lists.similarity_score().unwrap(),
+                "{lists:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn overflowing_push_leaves_the_lists_untouched() {
+    let mut lists = IncrementalLists::<i32>::new();
+    lists.push(i32::MAX, 0).unwrap();
+
+    assert!(lists.push(i32::MAX, i32::MAX).is_err());
+    assert_eq!(lists.len(), 1);
+    assert_eq!(*lists.total_distance(), i32::MAX);
+    assert_eq!(*lists.similarity_score(), 0);
+}
@@ -0,0 +1,89 @@
+use day1::{BigInt, LocationId, LocationLists, OverflowError};
+
+const TOTAL_DISTANCE: OverflowError = OverflowError {
+    computing: "total distance",
+};
+const SIMILARITY_SCORE: OverflowError = OverflowError {
+    computing: "similarity score",
+};
+
+/// Checks `T` against the exact answers `BigInt` gives for `input`, expecting an
+/// [`OverflowError`] for any answer too big for `T`.
+fn overflows<T: LocationId>(input: &str, total_distance: &str, similarity_score: &str) {
+    let lists: LocationLists<T> = LocationLists::parse(input).unwrap();
+    let big: LocationLists<BigInt> = LocationLists::parse(input).unwrap();
+
+    let expected_distance: BigInt = total_distance.parse().unwrap();
+    let expected_score: BigInt = similarity_score.parse().unwrap();
+
+    assert_eq!(
+        big.total_distance(),
+        Ok(expected_distance.clone()),
+        "{input}"
+    );
+    assert_eq!(
+        big.similarity_score(),
+        Ok(expected_score.clone()),
+        "{input}"
+    );
+
+    // Whatever doesn't fit in `T` should be reported as an overflow rather than wrapping.
+    let narrowed = |expected: BigInt, error| expected.to_string().parse::<T>().map_err(|_| error);
+
+    assert_eq!(
+        lists.total_distance(),
+        narrowed(expected_distance, TOTAL_DISTANCE),
+        "{input}"
+    );
+    assert_eq!(
+        lists.similarity_score(),
+        narrowed(expected_score, SIMILARITY_SCORE),
+        "{input}"
+    );
+}
+
+#[test]
+fn i32_overflow() {
+    // Each distance fits, but not their sum.
+    overflows::<i32>("0 2000000000\n0 2000000000\n", "4000000000", "0");
+    // A single distance is already too big.
+    overflows::<i32>("-2000000000 2000000000\n", "4000000000", "0");
+    overflows::<i32>(
+        "2000000000 2000000000\n2000000000 2000000000\n",
+        "0",
+        "8000000000",
+    );
+}
+
+#[test]
+fn i64_overflow() {
+    overflows::<i64>(
+        "0 9000000000000000000\n0 9000000000000000000\n",
+        "18000000000000000000",
+        "0",
+    );
+    overflows::<i64>(
+        "5000000000000000000 5000000000000000000\n",
+        "0",
+        "5000000000000000000",
+    );
+    overflows::<i64>(
+        "5000000000000000000 5000000000000000000\n5000000000000000000 1\n",
+        "4999999999999999999",
+        "10000000000000000000",
+    );
+}
+
+#[test]
+fn u64_overflow() {
+    overflows::<u64>(
+        "0 18000000000000000000\n0 18000000000000000000\n",
+        "36000000000000000000",
+        "0",
+    );
+    overflows::<u64>(
+        "10000000000000000000 10000000000000000000\n10000000000000000000 1\n",
+        "9999999999999999999",
+        "20000000000000000000",
+    );
+}
@@ -0,0 +1,99 @@
+use day1::{parse_line, LineError, LineErrorKind, LocationLists, ParseMode};
+
+fn error(line: usize, column: usize, token: &str, kind: LineErrorKind) -> LineError {
+    LineError {
+        line,
+        column,
+        token: token.to_string(),
+        kind,
+    }
+}
+
+#[test]
+fn each_kind_of_error() {
+    assert_eq!(parse_line::<i32>(1, "3   4"), Ok(Some((3, 4))));
+    assert_eq!(parse_line::<i32>(2, "   "), Ok(None));
+    assert_eq!(
+        parse_line::<i32>(3, "3   x4"),
+        Err(error(3, 5, "x4", LineErrorKind::InvalidNumber))
+    );
+    assert_eq!(
+        parse_line::<i32>(4, "  3  "),
+        Err(error(4, 4, "", LineErrorKind::MissingColumn))
+    );
+    assert_eq!(
+        parse_line::<i32>(5, "3 4 5"),
+        Err(error(5, 5, "5", LineErrorKind::ExtraColumn))
+    );
+}
+
+#[test]
+fn columns_are_counted_in_characters() {
+    // `é` is two bytes, so a byte count would put these a column further along.
+    assert_eq!(
+        parse_line::<i32>(1, "é 4"),
+        Err(error(1, 1, "é", LineErrorKind::InvalidNumber))
+    );
+    assert_eq!(
+        parse_line::<i32>(1, "3\u{a0}é4 5"),
+        Err(error(1, 3, "é4", LineErrorKind::InvalidNumber))
+    );
+    assert_eq!(
+        parse_line::<i32>(1, "3 4 ünd"),
+        Err(error(1, 5, "ünd", LineErrorKind::ExtraColumn))
+    );
+    assert_eq!(
+        parse_line::<i32>(1, "ü"),
+        Err(error(1, 1, "ü", LineErrorKind::InvalidNumber))
+    );
+    assert_eq!(
+        parse_line::<i32>(1, "  ü12"),
+        Err(error(1, 3, "ü12", LineErrorKind::InvalidNumber))
+    );
+}
+
+#[test]
+fn missing_column_points_past_the_last_character() {
+    // The ideographic space is whitespace three bytes long.
+    assert_eq!(
+        parse_line::<i32>(7, "\u{3000}12"),
+        Err(error(7, 4, "", LineErrorKind::MissingColumn))
+    );
+    assert_eq!(
+        parse_line::<i32>(8, "33\t"),
+        Err(error(8, 3, "", LineErrorKind::MissingColumn))
+    );
+}
+
+const INPUT: &str = "3   4\n4   x3\n\n2   5\n1\n3   9   9\n3   3\n";
+
+#[test]
+fn strict_mode_reports_every_bad_row() {
+    let errors = LocationLists::<i32>::parse(INPUT).unwrap_err().errors;
+
+    assert_eq!(
+        errors,
+        [
+            error(2, 5, "x3", LineErrorKind::InvalidNumber),
+            error(5, 2, "", LineErrorKind::MissingColumn),
+            error(6, 9, "9", LineErrorKind::ExtraColumn),
+        ]
+    );
+}
+
+#[test]
+fn lenient_mode_returns_the_skipped_rows() {
+    let (lists, skipped) =
+        LocationLists::<i32>::parse_with_mode(INPUT, ParseMode::Lenient).unwrap();
+
+    assert_eq!(lists.left, [3, 2, 3]);
+    assert_eq!(lists.right, [4, 5, 3]);
+    assert_eq!(
+        skipped.iter().map(|error| error.line).collect::<Vec<_>>(),
+        [2, 5, 6]
+    );
+    assert_eq!(
+        skipped[0].to_string(),
+        "line 2, column 5: `x3` is not a valid location ID"
+    );
+}
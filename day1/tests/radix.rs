@@ -0,0 +1,72 @@
+mod common;
+
+use day1::{par_radix_sort, BigInt, LocationId};
+
+use common::XorShift;
+
+fn matches_sort<T: LocationId>(ids: Vec<T>) {
+    let mut expected = ids.clone();
+    expected.sort();
+
+    let mut actual = ids;
+    par_radix_sort(&mut actual);
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn edge_cases() {
+    matches_sort::<i32>(vec![]);
+    matches_sort(vec![7i32]);
+    matches_sort(vec![i32::MAX, i32::MIN, 0, -1, 1, i32::MIN, i32::MAX]);
+    matches_sort(vec![
+        i64::MAX,
+        i64::MIN,
+        0,
+        -1,
+        1,
+        i64::MIN + 1,
+        i64::MAX - 1,
+    ]);
+    matches_sort(vec![5i64; 1000]);
+    matches_sort(vec![u64::MAX, 0, u64::MAX - 1, 1]);
+}
+
+#[test]
+fn random_lists() {
+    let mut rng = XorShift(0x2024_0010);
+
+    // Small lists finish with a comparison sort, big ones go through every radix pass.
+    for len in [10, 63, 64, 1_000, 100_000] {
+        // Negative IDs only, IDs around zero, a handful of values repeated, then the full range.
+        matches_sort::<i32>((0..len).map(|_| -(rng.below(1 << 30) as i32) - 1).collect());
+        matches_sort::<i32>((0..len).map(|_| rng.below(2_000) as i32 - 1_000).collect());
+        matches_sort::<i32>(
+            (0..len)
+                .map(|_| rng.below(4) as i32 * (i32::MAX / 3))
+                .collect(),
+        );
+        matches_sort::<i32>((0..len).map(|_| rng.next() as i32).collect());
+
+        matches_sort::<i64>((0..len).map(|_| -(rng.below(1 << 62) as i64) - 1).collect());
+        matches_sort::<i64>((0..len).map(|_| rng.below(2_000) as i64 - 1_000).collect());
+        matches_sort::<i64>(
+            (0..len)
+                .map(|_| [i64::MIN, -1, 0, i64::MAX][rng.below(4) as usize])
+                .collect(),
+        );
+        matches_sort::<i64>((0..len).map(|_| rng.next() as i64).collect());
+    }
+}
+
+#[test]
+fn big_ints_fall_back_to_a_comparison_sort() {
+    let mut rng = XorShift(0x2024_0110);
+
+    matches_sort::<BigInt>(
+        (0..1_000)
+            .map(|_| (BigInt::from(rng.next()) << 70) - BigInt::from(rng.next()))
+            .chain([BigInt::from(0), BigInt::from(-1), BigInt::from(i64::MIN)])
+            .collect(),
+    );
+}
@@ -0,0 +1,37 @@
+use day1::{CountMismatch, LocationLists};
+
+#[test]
+fn example_sections() {
+    let lists: LocationLists =
+        LocationLists::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n7   7\n7   4\n").unwrap();
+    let reconciliation = lists.reconcile();
+
+    assert_eq!(reconciliation.left_only, [(1, 1), (2, 1)]);
+    assert_eq!(reconciliation.right_only, [(5, 1), (9, 1)]);
+    assert_eq!(
+        reconciliation.count_mismatches,
+        [
+            CountMismatch {
+                id: 4,
+                left: 1,
+                right: 2
+            },
+            CountMismatch {
+                id: 7,
+                left: 2,
+                right: 1
+            }
+        ]
+    );
+    assert!(!reconciliation.is_empty());
+}
+
+#[test]
+fn same_ids_in_a_different_order() {
+    let lists = LocationLists {
+        left: vec![5, 1, 5, 2],
+        right: vec![2, 5, 1, 5],
+    };
+
+    assert!(lists.reconcile().is_empty());
+}
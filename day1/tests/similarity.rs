@@ -0,0 +1,41 @@
+mod common;
+
+use day1::{similarity_score, similarity_score_naive, LocationLists};
+
+use common::XorShift;
+
+#[test]
+fn matches_naive_on_generated_lists() {
+    let mut rng = XorShift(0x2024_0001);
+
+    for (len, max) in [(0, 10), (1, 10), (100, 10), (2_000, 500), (5_000, 100_000)] {
+        let left = rng.list(len, max);
+        let right = rng.list(len, max);
+
+        assert_eq!(
+            similarity_score(&left, &right).unwrap(),
+            similarity_score_naive(&left, &right).unwrap(),
+            "len {len}, max {max}"
+        );
+    }
+}
+
+#[test]
+fn example_input() {
+    let left = [3, 4, 2, 1, 3, 3];
+    let right = [4, 3, 5, 3, 9, 3];
+
+    assert_eq!(similarity_score(&left, &right), Ok(31));
+    assert_eq!(similarity_score_naive(&left, &right), Ok(31));
+}
+
+#[test]
+fn location_lists_example() {
+    let lists: LocationLists =
+        LocationLists::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+
+    assert_eq!(lists.left, [3, 4, 2, 1, 3, 3]);
+    assert_eq!(lists.right, [4, 3, 5, 3, 9, 3]);
+    assert_eq!(lists.total_distance(), Ok(11));
+    assert_eq!(lists.similarity_score(), Ok(31));
+}
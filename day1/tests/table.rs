@@ -0,0 +1,48 @@
+use day1::{LineErrorKind, LocationTable, ParseMode};
+
+const EXAMPLE: &str = "3   4   1\n4   3   2\n2   5   2\n1   3   9\n3   9   3\n3   3   3\n";
+
+#[test]
+fn pairwise_matrix_of_three_columns() {
+    let (table, skipped) =
+        LocationTable::<i32>::parse_with_mode(EXAMPLE, ParseMode::Strict).unwrap();
+    assert!(skipped.is_empty());
+    assert_eq!(table.columns.len(), 3);
+
+    let matrix = table.pairwise().unwrap();
+    assert_eq!(matrix.total_distance, [[0, 11, 6], [11, 0, 7], [6, 7, 0]]);
+    assert_eq!(
+        matrix.similarity_score,
+        [[34, 31, 23], [31, 45, 27], [23, 27, 30]]
+    );
+}
+
+#[test]
+fn width_is_the_most_common_row_width() {
+    let input = format!("\n1   2\n{EXAMPLE}7   8   9   10\n");
+    let (table, skipped) =
+        LocationTable::<i32>::parse_with_mode(&input, ParseMode::Lenient).unwrap();
+
+    assert_eq!(table.columns.len(), 3);
+    assert_eq!(table.columns[2], [1, 2, 2, 9, 3, 3]);
+    assert_eq!(
+        skipped
+            .iter()
+            .map(|error| (error.line, error.kind))
+            .collect::<Vec<_>>(),
+        [
+            (2, LineErrorKind::MissingColumn),
+            (9, LineErrorKind::ExtraColumn)
+        ]
+    );
+}
+
+#[test]
+fn explicit_width() {
+    let (table, skipped) =
+        LocationTable::<i32>::parse_with_columns("1   2\n3   4   5\n", 3, ParseMode::Lenient)
+            .unwrap();
+
+    assert_eq!(table.columns, [[3], [4], [5]]);
+    assert_eq!(skipped.len(), 1);
+}
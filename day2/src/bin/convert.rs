@@ -0,0 +1,84 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+};
+
+use anyhow::{anyhow, Result};
+use day2::{parse_report, report_skipped, BinaryReader, BinaryWriter, Options};
+
+/// Reads text reports line by line and writes them in the binary form.
+fn to_binary(input: impl BufRead, output: impl Write, options: &Options) -> Result<()> {
+    let mut writer = BinaryWriter::new(output)?;
+    let mut skipped = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        match parse_report(index + 1, &line?) {
+            Ok(Some(report)) => writer.write_report(&report.levels)?,
+            Ok(None) => {}
+            Err(error) if options.skip_bad => skipped.push(error),
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    writer.finish()?;
+    report_skipped(&skipped);
+
+    Ok(())
+}
+
+/// Writes binary reports back out as text, one report per line.
+fn to_text(input: impl BufRead, mut output: impl Write) -> Result<()> {
+    for report in BinaryReader::new(input)? {
+        let levels: Vec<String> = report?.levels.iter().map(i32::to_string).collect();
+        writeln!(output, "{}", levels.join(" "))?;
+    }
+
+    output.flush()?;
+    Ok(())
+}
+
+enum Direction {
+    ToBinary,
+    ToText,
+}
+
+// Usage: convert to-binary|to-text [INPUT [OUTPUT]]
+// Converts reports between the text and binary forms. Reads from stdin and writes to stdout when
+// no paths (or `-`) are given.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_binary()?;
+    options.expect_format_in(&[])?;
+
+    let mut args = options.extra.iter();
+    // Checked before anything is opened, so a typo doesn't truncate the output file.
+    let direction = match args.next().map(String::as_str) {
+        Some("to-binary") => Direction::ToBinary,
+        Some("to-text") => Direction::ToText,
+        Some(direction) => {
+            return Err(anyhow!(
+                "Unknown direction `{direction}`, expected to-binary or to-text"
+            ))
+        }
+        None => return Err(anyhow!("Expected to-binary or to-text")),
+    };
+    let input_path = args.next();
+    let output_path = args.next();
+    if let Some(arg) = args.next() {
+        return Err(anyhow!("Unexpected argument `{arg}`"));
+    }
+
+    let input: Box<dyn BufRead> = match input_path.map(String::as_str) {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+    let output: Box<dyn Write> = match output_path.map(String::as_str) {
+        None | Some("-") => Box::new(BufWriter::new(io::stdout().lock())),
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+    };
+
+    match direction {
+        Direction::ToBinary => to_binary(input, output, &options),
+        Direction::ToText => to_text(input, output),
+    }
+}
@@ -1,59 +1,26 @@
-use std::{fs::File, io::Read};
+use std::io;
 
 use anyhow::Result;
-
-fn find_safe(data: Vec<i32>) -> bool {
-    !data
-        .iter()
-        .zip(data.iter().skip(1))
-        .map(|(a, b)| {
-            if (((*a > 0) && (*b > 0)) || ((*a < 0) && (*b < 0)))
-                && a.abs() <= 3
-                && b.abs() <= 3
-                && b.abs() >= 1
-                && b.abs() >= 1
-            {
-                true
-            } else {
-                false
-            }
-        })
-        .any(|i| !i)
-}
+use day2::{report_skipped, write_statuses, Options, ReportStatus};
 
 fn main() -> Result<()> {
-    let mut file = File::open("./input.txt")?;
-    let mut data_string = String::new();
-    let _ = file.read_to_string(&mut data_string);
-
-    let data: Vec<Vec<i32>> = data_string
-        .split("\n")
-        .map(|line| {
-            line.split_whitespace()
-                .map(|i| i.parse::<i32>().unwrap())
-                .collect()
-        })
-        .collect();
+    let options = Options::from_env()?;
+    options.expect_no_extra()?;
 
-    let result = data.iter().fold(0, |acc, report| {
-        let changes = report
-            .iter()
-            .zip(report.iter().skip(1))
-            .map(|(a, b)| dbg!(b - a))
-            .collect::<Vec<i32>>();
+    let (data, skipped) = options.load_reports()?;
 
-        let safe = find_safe(changes);
-
-        println!("");
-
-        if safe {
-            acc + 1
-        } else {
-            acc
-        }
-    });
+    let statuses: Vec<ReportStatus> = data
+        .iter()
+        .map(|report| options.policy.status(&report.levels, false))
+        .collect();
 
-    println!("{result}");
+    write_statuses(
+        io::stdout().lock(),
+        &data,
+        &statuses,
+        options.format.unwrap_or_default(),
+    )?;
+    report_skipped(&skipped);
 
     Ok(())
 }
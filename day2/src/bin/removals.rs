@@ -0,0 +1,41 @@
+use anyhow::{anyhow, Result};
+use day2::{report_skipped, Options};
+
+// Usage: removals [--max-removals K]
+// Prints the fewest levels each report needs removed to be safe, then how many reports are safe
+// with at most K removals (1 by default, the same as part2).
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_format_in(&[])?;
+
+    let mut max_removals = 1;
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-removals" => {
+                max_removals = args
+                    .next()
+                    .ok_or(anyhow!("--max-removals needs a number"))?
+                    .parse()?
+            }
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let (data, skipped) = options.load_reports()?;
+
+    let result = data
+        .iter()
+        .filter(|report| {
+            let min_removals = options.policy.min_removals(&report.levels);
+            println!("Line {}: {min_removals}", report.line);
+
+            min_removals <= max_removals
+        })
+        .count();
+
+    println!("Safe with at most {max_removals} removal(s): {result}");
+    report_skipped(&skipped);
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use anyhow::Result;
+use day2::{report_skipped, Options, OutputFormat, Repair};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Row<'a> {
+    line: usize,
+    levels: &'a [i32],
+    #[serde(flatten)]
+    repair: Repair,
+}
+
+// Usage: repair [--format json]
+// Lists how each unsafe report could be made safe: every level the Problem Dampener could
+// remove, or failing that the fewest levels to change and what to change them to.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_extra()?;
+    options.expect_format_in(&[OutputFormat::Json])?;
+
+    let (data, skipped) = options.load_reports()?;
+
+    let rows: Vec<Row> = data
+        .iter()
+        .filter(|report| !options.policy.is_safe(&report.levels))
+        .map(|report| Row {
+            line: report.line,
+            levels: &report.levels,
+            repair: options.policy.repair(&report.levels),
+        })
+        .collect();
+
+    if options.format == Some(OutputFormat::Json) {
+        serde_json::to_writer_pretty(io::stdout().lock(), &rows)?;
+        println!();
+    } else {
+        rows.iter().for_each(|row| match &row.repair {
+            Repair {
+                repairable: false, ..
+            } => println!("Line {}: can't be made safe under this policy", row.line),
+            Repair {
+                removable,
+                edits: None,
+                ..
+            } => println!(
+                "Line {}: remove any one of the levels at index {}",
+                row.line,
+                join(
+                    removable
+                        .iter()
+                        .map(|index| format!("{index} ({})", row.levels[*index]))
+                )
+            ),
+            Repair {
+                edits: Some(edits), ..
+            } => println!(
+                "Line {}: change {} level(s): {}",
+                row.line,
+                edits.len(),
+                join(edits.iter().map(|edit| {
+                    format!("index {} from {} to {}", edit.index, edit.from, edit.to)
+                }))
+            ),
+        });
+    }
+
+    report_skipped(&skipped);
+
+    Ok(())
+}
+
+fn join(items: impl Iterator<Item = String>) -> String {
+    items.collect::<Vec<_>>().join(", ")
+}
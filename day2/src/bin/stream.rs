@@ -0,0 +1,45 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+use anyhow::{anyhow, Result};
+use day2::{report_skipped, Options, StreamEval};
+
+// Usage: stream [--batch-size N] [PATH]
+// Reads from stdin when no path (or `-`) is given, and prints both parts' safe counts.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_binary()?;
+    options.expect_format_in(&[])?;
+
+    let mut eval = StreamEval::default();
+    let mut path = None;
+
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--batch-size" => {
+                eval.batch_size = args
+                    .next()
+                    .ok_or(anyhow!("--batch-size needs a number"))?
+                    .parse()?
+            }
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let input: Box<dyn BufRead> = match path.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+
+    let totals = eval.totals(input, &options.policy, options.skip_bad)?;
+    report_skipped(&totals.skipped);
+
+    println!("Safe: {}", totals.safe);
+    println!("Safe with the Problem Dampener: {}", totals.safe_dampened);
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::io::{self, BufRead, ErrorKind, Read, Write};
+
+use crate::Report;
+
+/// Marks the start of a binary report file, followed by a format version byte.
+const MAGIC: &[u8; 4] = b"D2RB";
+const VERSION: u8 = 1;
+
+/// Writes reports in the compact binary form.
+///
+/// The file starts with a short header, then each report is its number of levels followed by
+/// the first level and the difference from each level to the next. Every number is a zigzag
+/// varint, so the small steps most reports are made of take a byte each.
+pub struct BinaryWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> BinaryWriter<W> {
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        Ok(Self { writer })
+    }
+
+    pub fn write_report(&mut self, levels: &[i32]) -> io::Result<()> {
+        write_varint(&mut self.writer, levels.len() as u64)?;
+
+        let mut previous = 0;
+        for &level in levels {
+            write_varint(&mut self.writer, zigzag(i64::from(level) - previous))?;
+            previous = i64::from(level);
+        }
+
+        Ok(())
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads reports back from the compact binary form, one at a time.
+///
+/// The binary form has no blank lines, so each report's `line` is its 1-based position in the
+/// file.
+pub struct BinaryReader<R: BufRead> {
+    reader: R,
+    line: usize,
+}
+
+impl<R: BufRead> BinaryReader<R> {
+    /// Fails if the input doesn't start with the binary header.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; 5];
+        reader.read_exact(&mut header).map_err(|_| not_binary())?;
+        if &header[..4] != MAGIC {
+            return Err(not_binary());
+        }
+        if header[4] != VERSION {
+            return Err(invalid(format!(
+                "unsupported binary report version {}",
+                header[4]
+            )));
+        }
+
+        Ok(Self { reader, line: 0 })
+    }
+
+    fn read_report(&mut self) -> io::Result<Option<Report>> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        self.line += 1;
+
+        let len = read_varint(&mut self.reader)?;
+        let mut levels = Vec::with_capacity(len.min(1024) as usize);
+        let mut level = 0i64;
+        for _ in 0..len {
+            level = level.saturating_add(unzigzag(read_varint(&mut self.reader)?));
+            levels.push(i32::try_from(level).map_err(|_| {
+                invalid(format!(
+                    "report {}: level {level} is out of range",
+                    self.line
+                ))
+            })?);
+        }
+
+        Ok(Some(Report {
+            line: self.line,
+            levels,
+        }))
+    }
+}
+
+impl<R: BufRead> Iterator for BinaryReader<R> {
+    type Item = io::Result<Report>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_report().transpose()
+    }
+}
+
+/// Reads every report from the compact binary form.
+pub fn read_binary_reports(reader: impl BufRead) -> io::Result<Vec<Report>> {
+    BinaryReader::new(reader)?.collect()
+}
+
+/// Writes every report in the compact binary form.
+pub fn write_binary_reports(writer: impl Write, reports: &[Report]) -> io::Result<()> {
+    let mut writer = BinaryWriter::new(writer)?;
+    for report in reports {
+        writer.write_report(&report.levels)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+fn write_varint(writer: &mut impl Write, mut value: u64) -> io::Result<()> {
+    let mut bytes = [0; 10];
+    let mut len = 0;
+    loop {
+        bytes[len] = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&bytes[..=len]);
+        }
+        bytes[len] |= 0x80;
+        len += 1;
+    }
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader
+            .read_exact(&mut byte)
+            .map_err(|error| match error.kind() {
+                ErrorKind::UnexpectedEof => invalid("binary reports end partway through a report"),
+                _ => error,
+            })?;
+
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(invalid("varint is longer than 10 bytes"))
+}
+
+fn not_binary() -> io::Error {
+    invalid("input is not in the binary report format")
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message.into())
+}
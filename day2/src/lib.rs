@@ -0,0 +1,17 @@
+pub use binary::{read_binary_reports, write_binary_reports, BinaryReader, BinaryWriter};
+pub use options::{report_skipped, Options};
+pub use output::{write_statuses, OutputFormat};
+pub use parse::{parse_report, parse_reports, parse_reports_skipping, Report, ReportError};
+pub use policy::SafetyPolicy;
+pub use repair::{LevelEdit, Repair};
+pub use status::{ReportStatus, UnsafeReason};
+pub use stream::{StreamEval, StreamTotals};
+
+mod binary;
+mod options;
+mod output;
+mod parse;
+mod policy;
+mod repair;
+mod status;
+mod stream;
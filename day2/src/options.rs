@@ -0,0 +1,142 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    parse_reports, parse_reports_skipping, read_binary_reports, OutputFormat, Report, ReportError,
+    SafetyPolicy,
+};
+
+/// Command line flags shared by the day2 binaries.
+///
+/// `--policy PATH` loads a [`SafetyPolicy`] from a TOML file, then `--min-step N`,
+/// `--max-step N`, `--monotonic`, `--non-monotonic` and `--plateaus N` override single fields
+/// of it. `--format count|table|json` picks how results are printed, and `--skip-bad` sets
+/// aside reports with invalid levels instead of failing on them. `--binary PATH` reads reports
+/// from a file in the compact binary form instead of `./input.txt`. Flags a binary handles
+/// itself are left in `extra`, in the order they were given.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub policy: SafetyPolicy,
+    /// `None` if `--format` wasn't given, for each binary's own default.
+    pub format: Option<OutputFormat>,
+    pub skip_bad: bool,
+    pub binary: Option<PathBuf>,
+    pub extra: Vec<String>,
+}
+
+impl Options {
+    pub fn from_env() -> Result<Self> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut policy_path = None;
+        let mut min_step = None;
+        let mut max_step = None;
+        let mut monotonic = None;
+        let mut allowed_plateaus = None;
+        let mut format = None;
+        let mut skip_bad = false;
+        let mut binary = None;
+        let mut extra = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(anyhow!("{arg} needs a value"));
+
+            match arg.as_str() {
+                "--policy" => policy_path = Some(value()?),
+                "--min-step" => min_step = Some(value()?.parse()?),
+                "--max-step" => max_step = Some(value()?.parse()?),
+                "--plateaus" => allowed_plateaus = Some(value()?.parse()?),
+                "--format" => format = Some(value()?.parse().map_err(|e: String| anyhow!(e))?),
+                "--skip-bad" => skip_bad = true,
+                "--binary" => binary = Some(PathBuf::from(value()?)),
+                "--monotonic" => monotonic = Some(true),
+                "--non-monotonic" => monotonic = Some(false),
+                _ => extra.push(arg),
+            }
+        }
+
+        let mut policy = match policy_path {
+            Some(path) => SafetyPolicy::from_file(path)?,
+            None => SafetyPolicy::default(),
+        };
+        policy.min_step = min_step.unwrap_or(policy.min_step);
+        policy.max_step = max_step.unwrap_or(policy.max_step);
+        policy.monotonic = monotonic.unwrap_or(policy.monotonic);
+        policy.allowed_plateaus = allowed_plateaus.unwrap_or(policy.allowed_plateaus);
+
+        Ok(Self {
+            policy,
+            format,
+            skip_bad,
+            binary,
+            extra,
+        })
+    }
+
+    /// Fails if any flag was left over for a binary that takes no flags of its own.
+    pub fn expect_no_extra(&self) -> Result<()> {
+        match self.extra.first() {
+            Some(arg) => Err(anyhow!("Unexpected argument `{arg}`")),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails on a `--format` that isn't one of `supported`, for the binaries that print only
+    /// some of the formats, or none of them.
+    pub fn expect_format_in(&self, supported: &[OutputFormat]) -> Result<()> {
+        match self.format {
+            Some(format) if !supported.contains(&format) => {
+                Err(anyhow!("--format {format} isn't supported here"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails on `--binary`, for the binaries that only read text reports.
+    pub fn expect_no_binary(&self) -> Result<()> {
+        match self.binary {
+            Some(_) => Err(anyhow!(
+                "--binary only applies to part1, part2, removals and repair"
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Options {
+    /// Parses the input's reports, returning any that were skipped by `--skip-bad`.
+    pub fn read_reports(&self, input: &str) -> Result<(Vec<Report>, Vec<ReportError>)> {
+        if self.skip_bad {
+            Ok(parse_reports_skipping(input))
+        } else {
+            Ok((parse_reports(input)?, Vec::new()))
+        }
+    }
+
+    /// Reads the reports from `--binary PATH` if it was given, or from `./input.txt`.
+    pub fn load_reports(&self) -> Result<(Vec<Report>, Vec<ReportError>)> {
+        match &self.binary {
+            Some(path) => Ok((
+                read_binary_reports(BufReader::new(File::open(path)?))?,
+                Vec::new(),
+            )),
+            None => self.read_reports(&fs::read_to_string("./input.txt")?),
+        }
+    }
+}
+
+/// Lists the reports skipped by `--skip-bad` on stderr.
+pub fn report_skipped(skipped: &[ReportError]) {
+    if !skipped.is_empty() {
+        eprintln!("Skipped {} bad report(s):", skipped.len());
+        skipped.iter().for_each(|error| eprintln!("  {error}"));
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use crate::{Report, ReportStatus, UnsafeReason};
+
+/// How the binaries print their results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Only the number of safe reports.
+    #[default]
+    Count,
+    /// A row per report with its status, then the count.
+    Table,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "count" => Ok(Self::Count),
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Unknown format `{s}`, expected count, table or json"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Count => write!(f, "count"),
+            Self::Table => write!(f, "table"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ReportRow<'a> {
+    line: usize,
+    levels: &'a [i32],
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    removed_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<UnsafeReason>,
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+    reports: Vec<ReportRow<'a>>,
+    safe: usize,
+}
+
+/// Writes each report's status, numbered by the line it came from, followed by the safe count.
+pub fn write_statuses(
+    mut writer: impl Write,
+    reports: &[Report],
+    statuses: &[ReportStatus],
+    format: OutputFormat,
+) -> io::Result<()> {
+    let safe = statuses.iter().filter(|status| status.is_safe()).count();
+
+    match format {
+        OutputFormat::Count => writeln!(writer, "{safe}"),
+        OutputFormat::Table => {
+            let levels: Vec<String> = reports
+                .iter()
+                .map(|report| {
+                    report
+                        .levels
+                        .iter()
+                        .map(|level| level.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect();
+            let number_width = reports
+                .iter()
+                .map(|report| report.line.to_string().len())
+                .max()
+                .unwrap_or(0)
+                .max("Line".len());
+            let levels_width = levels
+                .iter()
+                .map(|levels| levels.len())
+                .max()
+                .unwrap_or(0)
+                .max("Levels".len());
+
+            writeln!(
+                writer,
+                "{:>number_width$}  {:<levels_width$}  Status",
+                "Line", "Levels"
+            )?;
+
+            reports.iter().zip(&levels).zip(statuses).try_for_each(
+                |((report, levels), status)| {
+                    let status = match status {
+                        ReportStatus::Safe => "safe".to_string(),
+                        ReportStatus::SafeAfterRemoving(index) => {
+                            format!("safe after removing index {index}")
+                        }
+                        ReportStatus::Unsafe { index, reason } => {
+                            format!("unsafe: {reason} at index {index}")
+                        }
+                    };
+
+                    writeln!(
+                        writer,
+                        "{:>number_width$}  {levels:<levels_width$}  {status}",
+                        report.line
+                    )
+                },
+            )?;
+
+            writeln!(writer, "Safe: {safe}")
+        }
+        OutputFormat::Json => {
+            let reports = reports
+                .iter()
+                .zip(statuses)
+                .map(|(report, status)| {
+                    let mut row = ReportRow {
+                        line: report.line,
+                        levels: &report.levels,
+                        status: "safe",
+                        removed_index: None,
+                        index: None,
+                        reason: None,
+                    };
+
+                    match *status {
+                        ReportStatus::Safe => {}
+                        ReportStatus::SafeAfterRemoving(index) => {
+                            row.status = "safe_after_removing";
+                            row.removed_index = Some(index);
+                        }
+                        ReportStatus::Unsafe { index, reason } => {
+                            row.status = "unsafe";
+                            row.index = Some(index);
+                            row.reason = Some(reason);
+                        }
+                    }
+
+                    row
+                })
+                .collect();
+
+            serde_json::to_writer_pretty(&mut writer, &Output { reports, safe })?;
+            writeln!(writer)
+        }
+    }
+}
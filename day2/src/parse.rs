@@ -0,0 +1,87 @@
+use std::fmt;
+
+/// One line of levels from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The 1-based line the report was read from.
+    pub line: usize,
+    pub levels: Vec<i32>,
+}
+
+/// A level that isn't a valid number. `line` and `column` are 1-based, with `column` counted in
+/// characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: `{}` is not a valid level",
+            self.line, self.column, self.token
+        )
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+/// Reads one report from a line, or `None` if the line is blank.
+pub fn parse_report(line_number: usize, line: &str) -> Result<Option<Report>, ReportError> {
+    let mut levels = Vec::new();
+    let mut column = 0;
+    let mut token_start = None;
+
+    // Chained with a trailing space so the last token is finished off like the others.
+    for (index, char) in line.char_indices().chain([(line.len(), ' ')]) {
+        column += 1;
+
+        match (char.is_whitespace(), token_start) {
+            (false, None) => token_start = Some((index, column)),
+            (true, Some((start, start_column))) => {
+                let token = &line[start..index];
+                levels.push(token.parse().map_err(|_| ReportError {
+                    line: line_number,
+                    column: start_column,
+                    token: token.to_string(),
+                })?);
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    Ok((!levels.is_empty()).then_some(Report {
+        line: line_number,
+        levels,
+    }))
+}
+
+/// Reads one report per line, failing on the first invalid level.
+pub fn parse_reports(input: &str) -> Result<Vec<Report>, ReportError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| parse_report(index + 1, line).transpose())
+        .collect()
+}
+
+/// Reads one report per line, setting aside any report with an invalid level.
+pub fn parse_reports_skipping(input: &str) -> (Vec<Report>, Vec<ReportError>) {
+    let mut reports = Vec::new();
+    let mut skipped = Vec::new();
+
+    input
+        .lines()
+        .enumerate()
+        .for_each(|(index, line)| match parse_report(index + 1, line) {
+            Ok(Some(report)) => reports.push(report),
+            Ok(None) => {}
+            Err(error) => skipped.push(error),
+        });
+
+    (reports, skipped)
+}
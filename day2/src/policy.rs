@@ -0,0 +1,244 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+/// The rules a report has to follow to be safe.
+///
+/// The defaults are the puzzle's rules. Policies can be read from a TOML file with any of the
+/// fields below, for example:
+///
+/// ```toml
+/// min_step = 1
+/// max_step = 5
+/// monotonic = false
+/// allowed_plateaus = 2
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyPolicy {
+    /// The smallest change allowed between adjacent levels. At 0, levels may repeat freely.
+    pub min_step: u32,
+    /// The largest change allowed between adjacent levels.
+    pub max_step: u32,
+    /// Whether every change has to go in the same direction.
+    pub monotonic: bool,
+    /// How many times adjacent levels may repeat when `min_step` would otherwise forbid it.
+    pub allowed_plateaus: usize,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+            monotonic: true,
+            allowed_plateaus: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Increasing,
+    Decreasing,
+    /// Used when the policy isn't monotonic.
+    Either,
+}
+
+/// How a change between two adjacent levels measures up against a policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    Safe,
+    /// A repeated level, using up one of the allowed plateaus.
+    Plateau,
+    Unsafe,
+}
+
+impl SafetyPolicy {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub(crate) fn directions(&self) -> &'static [Direction] {
+        if self.monotonic {
+            &[Direction::Increasing, Direction::Decreasing]
+        } else {
+            &[Direction::Either]
+        }
+    }
+
+    pub(crate) fn step(&self, a: i32, b: i32, direction: Direction) -> Step {
+        let change = b as i64 - a as i64;
+
+        if change == 0 && self.min_step > 0 {
+            return Step::Plateau;
+        }
+
+        let in_direction = match direction {
+            Direction::Increasing => change >= 0,
+            Direction::Decreasing => change <= 0,
+            Direction::Either => true,
+        };
+
+        if in_direction && (self.min_step as i64..=self.max_step as i64).contains(&change.abs()) {
+            Step::Safe
+        } else {
+            Step::Unsafe
+        }
+    }
+
+    /// Plateaus used by a run that was at `plateaus` and takes the step from `a` to `b`, or
+    /// `None` if the step isn't allowed.
+    pub(crate) fn take_step(
+        &self,
+        plateaus: usize,
+        a: i32,
+        b: i32,
+        direction: Direction,
+    ) -> Option<usize> {
+        let plateaus = match self.step(a, b, direction) {
+            Step::Safe => plateaus,
+            Step::Plateau => plateaus + 1,
+            Step::Unsafe => return None,
+        };
+
+        (plateaus <= self.allowed_plateaus).then_some(plateaus)
+    }
+
+    pub fn is_safe(&self, report: &[i32]) -> bool {
+        self.directions().iter().any(|&direction| {
+            report
+                .windows(2)
+                .try_fold(0, |plateaus, pair| {
+                    self.take_step(plateaus, pair[0], pair[1], direction)
+                })
+                .is_some()
+        })
+    }
+
+    /// Whether a report is safe once the Problem Dampener removes at most one level, in one pass.
+    pub fn is_safe_dampened(&self, report: &[i32]) -> bool {
+        self.is_safe_with_removals(report, 1)
+    }
+
+    /// Whether a report is safe once at most `k` levels are removed, in O(n·k²).
+    ///
+    /// For each direction this tracks the fewest plateaus a safe run can have used when it keeps
+    /// the current level, for every number of levels removed before it. With at most `k`
+    /// removals the previous kept level is never more than `k + 1` places back, so only the
+    /// states of the last `k + 1` levels are kept.
+    pub fn is_safe_with_removals(&self, report: &[i32], k: usize) -> bool {
+        let n = report.len();
+        if n <= k + 1 {
+            return true;
+        }
+
+        let window = k + 1;
+
+        // ending[(i % window) * window + r]: the fewest plateaus used by a safe run keeping
+        // level i, having removed r of the levels before it. Both buffers are allocated once and
+        // reset for each direction and level.
+        let mut ending: Vec<Option<usize>> = vec![None; window * window];
+        let mut current: Vec<Option<usize>> = vec![None; window];
+
+        self.directions().iter().any(|&direction| {
+            ending.fill(None);
+
+            for i in 0..n {
+                current.fill(None);
+
+                // Removing every level before this one.
+                if i <= k {
+                    current[i] = Some(0);
+                }
+
+                for skipped in 0..window {
+                    let Some(j) = i.checked_sub(1 + skipped) else {
+                        break;
+                    };
+                    let previous = &ending[(j % window) * window..][..window];
+
+                    for removed in skipped..window {
+                        let plateaus = previous[removed - skipped].and_then(|plateaus| {
+                            self.take_step(plateaus, report[j], report[i], direction)
+                        });
+
+                        if plateaus.is_some()
+                            && (current[removed].is_none() || plateaus < current[removed])
+                        {
+                            current[removed] = plateaus;
+                        }
+                    }
+                }
+
+                ending[(i % window) * window..][..window].copy_from_slice(&current);
+            }
+
+            // The run can end on any of the last `window` levels, removing the ones after it.
+            (n - window..n).any(|i| {
+                let after = n - 1 - i;
+                ending[(i % window) * window..][..window - after]
+                    .iter()
+                    .any(Option::is_some)
+            })
+        })
+    }
+
+    /// The fewest levels that have to be removed for a report to be safe, in O(n²·p) where p is
+    /// the number of allowed plateaus.
+    ///
+    /// This finds the longest safe run that can be kept, tracking for each level the most levels
+    /// a safe run ending there can keep for each number of plateaus it uses.
+    pub fn min_removals(&self, report: &[i32]) -> usize {
+        let n = report.len();
+        let plateaus = self.allowed_plateaus.min(n.saturating_sub(1));
+
+        let longest_run = self
+            .directions()
+            .iter()
+            .map(|&direction| {
+                // longest[i][p]: the most levels kept by a safe run ending at level i using p
+                // plateaus, or 0 if there's no such run.
+                let mut longest = vec![vec![0; plateaus + 1]; n];
+
+                for i in 0..n {
+                    longest[i][0] = 1;
+
+                    for j in 0..i {
+                        for used in 0..=plateaus {
+                            let kept = longest[j][used];
+                            if kept == 0 {
+                                continue;
+                            }
+
+                            if let Some(used) =
+                                self.take_step(used, report[j], report[i], direction)
+                            {
+                                longest[i][used] = longest[i][used].max(kept + 1);
+                            }
+                        }
+                    }
+                }
+
+                longest.into_iter().flatten().max().unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0);
+
+        n - longest_run
+    }
+
+    /// The Problem Dampener by brute force, checking the report with each level removed in turn.
+    ///
+    /// This is O(n²) and only kept around to check [`SafetyPolicy::is_safe_dampened`] against.
+    pub fn is_safe_dampened_brute(&self, report: &[i32]) -> bool {
+        self.is_safe(report)
+            || (0..report.len()).any(|i| {
+                let mut cloned_report = report.to_vec();
+                cloned_report.remove(i);
+
+                self.is_safe(&cloned_report)
+            })
+    }
+}
@@ -0,0 +1,331 @@
+use serde::Serialize;
+
+use crate::{policy::Direction, SafetyPolicy};
+
+/// Changing one level of a report to a new value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LevelEdit {
+    pub index: usize,
+    pub from: i32,
+    /// Edits can step outside the range of the input's levels, so this is wider than a level.
+    pub to: i64,
+}
+
+/// How an unsafe report could be made safe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Repair {
+    /// Every index the Problem Dampener could remove to make the report safe.
+    pub removable: Vec<usize>,
+    /// The fewest edits that make the report safe, only worked out for reports the dampener
+    /// can't fix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edits: Option<Vec<LevelEdit>>,
+    /// Whether anything makes the report safe. It's `false` when no report of this length can
+    /// be safe under the policy.
+    pub repairable: bool,
+}
+
+/// How a safe run reached a kept level, for rebuilding the edits afterwards.
+#[derive(Debug, Clone, Copy)]
+enum Parent {
+    /// Every level before this one is edited.
+    Start,
+    /// The previous kept level, the plateaus used up to it, and how many of the steps in
+    /// between are plateaus.
+    Kept {
+        index: usize,
+        plateaus: usize,
+        zero_steps: usize,
+    },
+}
+
+impl SafetyPolicy {
+    /// The levels the Problem Dampener could remove from a report, or failing that the fewest
+    /// edits that make it safe.
+    pub fn repair(&self, report: &[i32]) -> Repair {
+        let removable = self.removable_levels(report);
+        if !removable.is_empty() {
+            return Repair {
+                removable,
+                edits: None,
+                repairable: true,
+            };
+        }
+
+        let edits = self.min_edits(report);
+        Repair {
+            removable,
+            repairable: edits.is_some(),
+            edits,
+        }
+    }
+
+    /// Every index whose removal leaves the report safe, in O(n).
+    ///
+    /// For each direction this finds the fewest plateaus needed for every prefix and suffix of
+    /// the report to be safe, then checks whether the prefix before each level joins up with the
+    /// suffix after it.
+    pub fn removable_levels(&self, report: &[i32]) -> Vec<usize> {
+        let n = report.len();
+        let mut removable = vec![false; n];
+
+        for &direction in self.directions() {
+            let mut prefix: Vec<Option<usize>> = vec![Some(0); n];
+            for i in 1..n {
+                prefix[i] = prefix[i - 1].and_then(|plateaus| {
+                    self.take_step(plateaus, report[i - 1], report[i], direction)
+                });
+            }
+
+            let mut suffix: Vec<Option<usize>> = vec![Some(0); n];
+            for i in (0..n.saturating_sub(1)).rev() {
+                suffix[i] = suffix[i + 1].and_then(|plateaus| {
+                    self.take_step(plateaus, report[i], report[i + 1], direction)
+                });
+            }
+
+            for (index, removable) in removable.iter_mut().enumerate() {
+                let before = index.checked_sub(1).map(|i| (i, prefix[i]));
+                let after = (index + 1 < n).then(|| (index + 1, suffix[index + 1]));
+
+                *removable |= match (before, after) {
+                    (None, None) => true,
+                    (Some((_, plateaus)), None) | (None, Some((_, plateaus))) => plateaus.is_some(),
+                    (Some((i, before)), Some((j, after))) => before
+                        .zip(after)
+                        .and_then(|(before, after)| {
+                            self.take_step(before + after, report[i], report[j], direction)
+                        })
+                        .is_some(),
+                };
+            }
+        }
+
+        (0..n).filter(|&index| removable[index]).collect()
+    }
+
+    /// The fewest level edits that make a report safe, or `None` if no report of this length
+    /// can be safe under the policy.
+    ///
+    /// The levels that aren't edited form a run where each pair of neighbours is far enough
+    /// apart for the edited levels between them to be filled in with allowed steps. This finds
+    /// the longest such run, tracking for each level the longest run that ends there for each
+    /// number of plateaus used.
+    pub fn min_edits(&self, report: &[i32]) -> Option<Vec<LevelEdit>> {
+        let n = report.len();
+        if n == 0 {
+            return Some(Vec::new());
+        }
+
+        let cap = self.allowed_plateaus.min(n);
+        let mut best: Option<(usize, Vec<LevelEdit>)> = None;
+
+        for &direction in self.directions() {
+            // runs[i][p]: the longest run of kept levels ending at i using p plateaus.
+            let mut runs: Vec<Vec<Option<(usize, Parent)>>> = vec![vec![None; cap + 1]; n];
+
+            for i in 0..n {
+                if let Some(plateaus) = self.edge_plateaus(i, cap) {
+                    runs[i][plateaus] = Some((1, Parent::Start));
+                }
+
+                for j in 0..i {
+                    for plateaus in 0..=cap {
+                        let Some((kept, _)) = runs[j][plateaus] else {
+                            continue;
+                        };
+
+                        let steps = i - j;
+                        // With no minimum step, repeated levels are ordinary steps.
+                        let max_zero_steps = if self.min_step == 0 {
+                            0
+                        } else {
+                            steps.min(cap - plateaus)
+                        };
+
+                        for zero_steps in 0..=max_zero_steps {
+                            if !self.gap_fits(report[j], report[i], steps - zero_steps, direction) {
+                                continue;
+                            }
+
+                            let used = plateaus + zero_steps;
+                            if runs[i][used].is_none_or(|(best, _)| best < kept + 1) {
+                                runs[i][used] = Some((
+                                    kept + 1,
+                                    Parent::Kept {
+                                        index: j,
+                                        plateaus,
+                                        zero_steps,
+                                    },
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let end = (0..n)
+                .flat_map(|i| (0..=cap).map(move |plateaus| (i, plateaus)))
+                .filter(|&(i, plateaus)| {
+                    self.edge_plateaus(n - 1 - i, cap)
+                        .is_some_and(|after| plateaus + after <= cap)
+                })
+                .filter_map(|(i, plateaus)| Some((runs[i][plateaus]?.0, i, plateaus)))
+                .max();
+
+            let Some((kept, last, plateaus)) = end else {
+                continue;
+            };
+
+            if best.as_ref().is_none_or(|(best, _)| *best < kept) {
+                let edits = self.rebuild_edits(report, &runs, last, plateaus, direction);
+                best = Some((kept, edits));
+            }
+        }
+
+        best.map(|(_, edits)| edits)
+    }
+
+    /// Plateaus needed for `steps` steps between freely chosen levels, which is none unless the
+    /// policy has no allowed non-zero step.
+    fn edge_plateaus(&self, steps: usize, cap: usize) -> Option<usize> {
+        let plateaus = if self.min_step <= self.max_step {
+            0
+        } else {
+            steps
+        };
+
+        (plateaus <= cap).then_some(plateaus)
+    }
+
+    /// Whether exactly `steps` allowed non-zero steps can get from level `a` to level `b`.
+    fn gap_fits(&self, a: i32, b: i32, steps: usize, direction: Direction) -> bool {
+        let change = b as i64 - a as i64;
+        let steps = steps as i64;
+        let (min, max) = (self.min_step as i64, self.max_step as i64);
+
+        if steps == 0 {
+            return change == 0;
+        }
+        if min > max {
+            return false;
+        }
+
+        match direction {
+            Direction::Increasing => (steps * min..=steps * max).contains(&change),
+            Direction::Decreasing => (steps * min..=steps * max).contains(&-change),
+            Direction::Either => (0..=steps).any(|up| {
+                let down = steps - up;
+                (up * min - down * max..=up * max - down * min).contains(&change)
+            }),
+        }
+    }
+
+    fn rebuild_edits(
+        &self,
+        report: &[i32],
+        runs: &[Vec<Option<(usize, Parent)>>],
+        last: usize,
+        plateaus: usize,
+        direction: Direction,
+    ) -> Vec<LevelEdit> {
+        let mut values: Vec<i64> = report.iter().map(|&level| level as i64).collect();
+
+        // Levels outside the run carry on from its ends with the smallest allowed step, or
+        // repeat them if the policy has no usable step.
+        let edge_step = if self.min_step <= self.max_step {
+            self.min_step as i64
+        } else {
+            0
+        };
+        let edge_step = match direction {
+            Direction::Decreasing => -edge_step,
+            _ => edge_step,
+        };
+
+        for index in last + 1..report.len() {
+            values[index] = values[index - 1] + edge_step;
+        }
+
+        let (mut current, mut plateaus) = (last, plateaus);
+        loop {
+            let (_, parent) = runs[current][plateaus].expect("every level of a run was recorded");
+
+            match parent {
+                Parent::Start => {
+                    for index in (0..current).rev() {
+                        values[index] = values[index + 1] - edge_step;
+                    }
+                    break;
+                }
+                Parent::Kept {
+                    index,
+                    plateaus: previous_plateaus,
+                    zero_steps,
+                } => {
+                    let steps = self.fill_gap(
+                        report[index] as i64,
+                        report[current] as i64,
+                        current - index - zero_steps,
+                        direction,
+                    );
+
+                    let mut level = report[index] as i64;
+                    steps
+                        .into_iter()
+                        .chain(std::iter::repeat_n(0, zero_steps))
+                        .take(current - index - 1)
+                        .enumerate()
+                        .for_each(|(offset, change)| {
+                            level += change;
+                            values[index + 1 + offset] = level;
+                        });
+
+                    current = index;
+                    plateaus = previous_plateaus;
+                }
+            }
+        }
+
+        values
+            .into_iter()
+            .zip(report)
+            .enumerate()
+            .filter(|(_, (to, &from))| *to != from as i64)
+            .map(|(index, (to, &from))| LevelEdit { index, from, to })
+            .collect()
+    }
+
+    /// Splits the change from `a` to `b` into `steps` allowed non-zero steps, which
+    /// [`SafetyPolicy::gap_fits`] has already found to be possible.
+    fn fill_gap(&self, a: i64, b: i64, steps: usize, direction: Direction) -> Vec<i64> {
+        let change = b - a;
+        let (min, max) = (self.min_step as i64, self.max_step as i64);
+
+        let (up, down, up_total, down_total) = match direction {
+            Direction::Increasing => (steps, 0, change, 0),
+            Direction::Decreasing => (0, steps, 0, -change),
+            Direction::Either => (0..=steps)
+                .find_map(|up| {
+                    let down = steps - up;
+                    let (up_steps, down_steps) = (up as i64, down as i64);
+
+                    // The smallest total going down that lets the total going up fit too.
+                    let low = (down_steps * min).max(up_steps * min - change);
+                    let high = (down_steps * max).min(up_steps * max - change);
+                    (low <= high).then_some((up, down, change + low, low))
+                })
+                .expect("the gap was checked to fit"),
+        };
+
+        let split = |total: i64, count: usize| {
+            let divisor = count.max(1) as i64;
+            let (base, extra) = (total / divisor, (total % divisor) as usize);
+            (0..count).map(move |i| base + i64::from(i < extra))
+        };
+
+        split(up_total, up)
+            .chain(split(down_total, down).map(|step| -step))
+            .collect()
+    }
+}
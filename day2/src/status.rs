@@ -0,0 +1,118 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    policy::{Direction, Step},
+    SafetyPolicy,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsafeReason {
+    /// The levels changed direction in a policy that needs them to be monotonic.
+    DirectionFlip,
+    StepTooLarge,
+    StepTooSmall,
+    /// A repeated level, after all of the allowed plateaus were used up.
+    ZeroStep,
+}
+
+impl fmt::Display for UnsafeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DirectionFlip => write!(f, "direction flip"),
+            Self::StepTooLarge => write!(f, "step too large"),
+            Self::StepTooSmall => write!(f, "step too small"),
+            Self::ZeroStep => write!(f, "zero step"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportStatus {
+    Safe,
+    /// Safe once the Problem Dampener removes the level at this index.
+    SafeAfterRemoving(usize),
+    /// `index` is the first level that can't follow the one before it.
+    Unsafe {
+        index: usize,
+        reason: UnsafeReason,
+    },
+}
+
+impl ReportStatus {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Self::Unsafe { .. })
+    }
+}
+
+impl SafetyPolicy {
+    /// Classifies a report, letting the Problem Dampener remove a level when `dampened` is set.
+    pub fn status(&self, report: &[i32], dampened: bool) -> ReportStatus {
+        if self.is_safe(report) {
+            return ReportStatus::Safe;
+        }
+
+        // The prefix and suffix pass finds every removable level in one go, so this stays linear.
+        if dampened {
+            if let Some(&index) = self.removable_levels(report).first() {
+                return ReportStatus::SafeAfterRemoving(index);
+            }
+        }
+
+        let (index, reason) = self
+            .first_unsafe_level(report)
+            .expect("a report that isn't safe has an unsafe level");
+
+        ReportStatus::Unsafe { index, reason }
+    }
+
+    /// Walks a report in order, taking its direction from the first change, and finds the first
+    /// level that breaks the policy.
+    fn first_unsafe_level(&self, report: &[i32]) -> Option<(usize, UnsafeReason)> {
+        let mut direction = if self.monotonic {
+            None
+        } else {
+            Some(Direction::Either)
+        };
+        let mut plateaus = 0;
+
+        report.windows(2).enumerate().find_map(|(i, pair)| {
+            let change = pair[1] as i64 - pair[0] as i64;
+            let index = i + 1;
+
+            if direction.is_none() && change != 0 {
+                direction = Some(if change > 0 {
+                    Direction::Increasing
+                } else {
+                    Direction::Decreasing
+                });
+            }
+
+            match self.step(pair[0], pair[1], direction.unwrap_or(Direction::Either)) {
+                Step::Safe => None,
+                Step::Plateau => {
+                    plateaus += 1;
+                    (plateaus > self.allowed_plateaus).then_some((index, UnsafeReason::ZeroStep))
+                }
+                Step::Unsafe => {
+                    let against_direction = matches!(
+                        (direction, change.signum()),
+                        (Some(Direction::Increasing), -1) | (Some(Direction::Decreasing), 1)
+                    );
+
+                    let reason = if against_direction {
+                        UnsafeReason::DirectionFlip
+                    } else if change.unsigned_abs() > self.max_step as u64 {
+                        UnsafeReason::StepTooLarge
+                    } else {
+                        UnsafeReason::StepTooSmall
+                    };
+
+                    Some((index, reason))
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use std::io::BufRead;
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::{parse_report, ReportError, SafetyPolicy};
+
+/// Counts safe reports in input too big to hold in memory.
+///
+/// Lines are read in batches of `batch_size`, and each batch is parsed and checked in parallel
+/// before the next one is read, so only one batch is held at a time.
+#[derive(Debug, Clone)]
+pub struct StreamEval {
+    pub batch_size: usize,
+}
+
+impl Default for StreamEval {
+    fn default() -> Self {
+        Self { batch_size: 65_536 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamTotals {
+    pub reports: usize,
+    /// The part1 answer.
+    pub safe: usize,
+    /// The part2 answer, with the Problem Dampener.
+    pub safe_dampened: usize,
+    pub skipped: Vec<ReportError>,
+}
+
+impl StreamEval {
+    /// Fails on the first invalid level unless `skip_bad` is set, in which case bad reports
+    /// are left out of the counts and returned in `skipped`.
+    pub fn totals(
+        &self,
+        reader: impl BufRead,
+        policy: &SafetyPolicy,
+        skip_bad: bool,
+    ) -> Result<StreamTotals> {
+        let batch_size = self.batch_size.max(1);
+
+        let mut totals = StreamTotals::default();
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut lines = reader.lines();
+        let mut first_line = 1;
+
+        loop {
+            batch.clear();
+            for line in lines.by_ref().take(batch_size) {
+                batch.push(line?);
+            }
+            if batch.is_empty() {
+                break;
+            }
+
+            let results: Vec<_> = batch
+                .par_iter()
+                .enumerate()
+                .map(|(index, line)| {
+                    parse_report(first_line + index, line).map(|report| {
+                        report.map(|report| {
+                            (
+                                policy.is_safe(&report.levels),
+                                policy.is_safe_dampened(&report.levels),
+                            )
+                        })
+                    })
+                })
+                .collect();
+
+            for result in results {
+                match result {
+                    Ok(Some((safe, safe_dampened))) => {
+                        totals.reports += 1;
+                        totals.safe += usize::from(safe);
+                        totals.safe_dampened += usize::from(safe_dampened);
+                    }
+                    Ok(None) => {}
+                    Err(error) if skip_bad => totals.skipped.push(error),
+                    Err(error) => return Err(error.into()),
+                }
+            }
+
+            first_line += batch.len();
+        }
+
+        Ok(totals)
+    }
+}
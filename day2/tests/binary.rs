@@ -0,0 +1,47 @@
+mod common;
+
+use day2::{read_binary_reports, write_binary_reports, Report};
+
+use common::XorShift;
+
+#[test]
+fn round_trips_random_reports() {
+    let mut rng = XorShift(0x2024_0019);
+
+    let reports: Vec<Report> = (1..=10_000)
+        .map(|line| {
+            let levels = (0..rng.below(12))
+                .map(|_| match rng.below(10) {
+                    0 => i32::MIN,
+                    1 => i32::MAX,
+                    2 => rng.next() as i32,
+                    _ => rng.below(100) as i32,
+                })
+                .collect();
+
+            Report { line, levels }
+        })
+        .collect();
+
+    let mut bytes = Vec::new();
+    write_binary_reports(&mut bytes, &reports).unwrap();
+
+    assert_eq!(read_binary_reports(bytes.as_slice()).unwrap(), reports);
+}
+
+#[test]
+fn rejects_text_and_truncated_input() {
+    assert!(read_binary_reports("7 6 4 2 1\n".as_bytes()).is_err());
+
+    let mut bytes = Vec::new();
+    let report = Report {
+        line: 1,
+        levels: vec![7, 6, 4, 2, 1],
+    };
+    write_binary_reports(&mut bytes, &[report]).unwrap();
+
+    // Five bytes is just the header, which is a valid file with no reports.
+    for len in (0..5).chain(6..bytes.len()) {
+        assert!(read_binary_reports(&bytes[..len]).is_err(), "{len} bytes");
+    }
+}
@@ -0,0 +1,18 @@
+// Shared by the integration tests and benches, which each use only some of it.
+#![allow(dead_code)]
+
+/// Small xorshift generator, so generated test data is reproducible without extra dependencies.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, max: u64) -> u64 {
+        self.next() % max
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use day2::SafetyPolicy;
+
+use common::XorShift;
+
+#[test]
+fn matches_brute_force_on_random_reports() {
+    let mut rng = XorShift(0x2024_0002);
+
+    for _ in 0..200_000 {
+        let len = rng.below(10) as usize;
+        let mut level = rng.below(20) as i32;
+
+        // Mostly small steps, so plenty of the reports are safe or one level away from it.
+        let report: Vec<i32> = (0..len)
+            .map(|_| {
+                level += rng.below(9) as i32 - 4;
+                level
+            })
+            .collect();
+
+        let policy = SafetyPolicy::default();
+
+        assert_eq!(
+            policy.is_safe_dampened(&report),
+            policy.is_safe_dampened_brute(&report),
+            "{report:?}"
+        );
+    }
+}
+
+#[test]
+fn matches_brute_force_under_random_policies() {
+    let mut rng = XorShift(0x2024_0013);
+
+    for _ in 0..200_000 {
+        let policy = SafetyPolicy {
+            min_step: rng.below(3) as u32,
+            max_step: 1 + rng.below(4) as u32,
+            monotonic: rng.below(2) == 0,
+            allowed_plateaus: rng.below(3) as usize,
+        };
+
+        let len = rng.below(10) as usize;
+        let report: Vec<i32> = (0..len).map(|_| rng.below(8) as i32).collect();
+
+        assert_eq!(
+            policy.is_safe_dampened(&report),
+            policy.is_safe_dampened_brute(&report),
+            "{policy:?} {report:?}"
+        );
+    }
+}
+
+#[test]
+fn example_reports() {
+    let reports = [
+        ([7, 6, 4, 2, 1], true),
+        ([1, 2, 7, 8, 9], false),
+        ([9, 7, 6, 2, 1], false),
+        ([1, 3, 2, 4, 5], true),
+        ([8, 6, 4, 4, 1], true),
+        ([1, 3, 6, 7, 9], true),
+    ];
+
+    for (report, safe) in reports {
+        let policy = SafetyPolicy::default();
+
+        assert_eq!(policy.is_safe_dampened(&report), safe, "{report:?}");
+        assert_eq!(policy.is_safe_dampened_brute(&report), safe, "{report:?}");
+    }
+}
+
+/// The fewest removals found by trying every subset of levels to remove.
+fn min_removals_brute(policy: &SafetyPolicy, report: &[i32]) -> usize {
+    (0u32..1 << report.len())
+        .filter_map(|removed| {
+            let kept: Vec<i32> = report
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| removed & (1 << i) == 0)
+                .map(|(_, level)| *level)
+                .collect();
+
+            policy
+                .is_safe(&kept)
+                .then_some(removed.count_ones() as usize)
+        })
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn removal_counts_match_brute_force() {
+    let mut rng = XorShift(0x2024_0014);
+
+    for _ in 0..20_000 {
+        let policy = SafetyPolicy {
+            min_step: rng.below(3) as u32,
+            max_step: 1 + rng.below(4) as u32,
+            monotonic: rng.below(2) == 0,
+            allowed_plateaus: rng.below(3) as usize,
+        };
+
+        let len = rng.below(9) as usize;
+        let report: Vec<i32> = (0..len).map(|_| rng.below(8) as i32).collect();
+
+        let min_removals = policy.min_removals(&report);
+        assert_eq!(
+            min_removals,
+            min_removals_brute(&policy, &report),
+            "{policy:?} {report:?}"
+        );
+
+        for k in 0..=len {
+            assert_eq!(
+                policy.is_safe_with_removals(&report, k),
+                k >= min_removals,
+                "{policy:?} {report:?} k = {k}"
+            );
+        }
+    }
+}
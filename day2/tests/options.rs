@@ -0,0 +1,106 @@
+use std::io::Write;
+
+use day2::{Options, OutputFormat, SafetyPolicy};
+use tempfile::NamedTempFile;
+
+const POLICY: &str = "min_step = 2\nmax_step = 5\nmonotonic = false\nallowed_plateaus = 1\n";
+
+fn options(args: &[&str]) -> Options {
+    Options::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+}
+
+fn policy_file(toml: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(toml.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn policy_from_a_toml_file() {
+    let file = policy_file(POLICY);
+    assert_eq!(
+        SafetyPolicy::from_file(file.path()).unwrap(),
+        SafetyPolicy {
+            min_step: 2,
+            max_step: 5,
+            monotonic: false,
+            allowed_plateaus: 1,
+        }
+    );
+
+    // Fields left out keep the puzzle's values.
+    let file = policy_file("max_step = 4\n");
+    assert_eq!(
+        SafetyPolicy::from_file(file.path()).unwrap(),
+        SafetyPolicy {
+            max_step: 4,
+            ..SafetyPolicy::default()
+        }
+    );
+}
+
+#[test]
+fn unknown_policy_fields_are_rejected() {
+    let file = policy_file("max_steps = 4\n");
+    assert!(SafetyPolicy::from_file(file.path()).is_err());
+
+    let path = file.path().to_str().unwrap();
+    assert!(Options::parse(["--policy".to_string(), path.to_string()]).is_err());
+}
+
+#[test]
+fn flags_override_the_policy_file() {
+    let file = policy_file(POLICY);
+    let path = file.path().to_str().unwrap();
+
+    assert_eq!(
+        options(&["--policy", path]).policy,
+        SafetyPolicy::from_file(path).unwrap()
+    );
+    assert_eq!(
+        options(&[
+            "--max-step",
+            "7",
+            "--policy",
+            path,
+            "--monotonic",
+            "--plateaus",
+            "0"
+        ])
+        .policy,
+        SafetyPolicy {
+            min_step: 2,
+            max_step: 7,
+            monotonic: true,
+            allowed_plateaus: 0,
+        }
+    );
+    assert_eq!(
+        options(&["--min-step", "0", "--non-monotonic"]).policy,
+        SafetyPolicy {
+            min_step: 0,
+            monotonic: false,
+            ..SafetyPolicy::default()
+        }
+    );
+}
+
+#[test]
+fn flags_a_binary_ignores_are_rejected() {
+    assert_eq!(options(&[]).format, None);
+    assert!(options(&[]).expect_format_in(&[]).is_ok());
+    assert!(options(&["--format", "count"])
+        .expect_format_in(&[])
+        .is_err());
+    assert!(options(&["--format", "json"])
+        .expect_format_in(&[OutputFormat::Json])
+        .is_ok());
+    assert!(options(&["--format", "table"])
+        .expect_format_in(&[OutputFormat::Json])
+        .is_err());
+
+    assert!(options(&[]).expect_no_binary().is_ok());
+    assert!(options(&["--binary", "reports.bin"])
+        .expect_no_binary()
+        .is_err());
+}
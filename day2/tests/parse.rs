@@ -0,0 +1,77 @@
+use day2::{parse_report, parse_reports, parse_reports_skipping, Options, Report, ReportError};
+
+fn error(line: usize, column: usize, token: &str) -> ReportError {
+    ReportError {
+        line,
+        column,
+        token: token.to_string(),
+    }
+}
+
+fn report(line: usize, levels: &[i32]) -> Report {
+    Report {
+        line,
+        levels: levels.to_vec(),
+    }
+}
+
+#[test]
+fn reports_and_blank_lines() {
+    assert_eq!(
+        parse_report(1, "7 6  4\t2"),
+        Ok(Some(report(1, &[7, 6, 4, 2])))
+    );
+    assert_eq!(parse_report(2, "-1 +2"), Ok(Some(report(2, &[-1, 2]))));
+    assert_eq!(parse_report(3, " \t "), Ok(None));
+    assert_eq!(parse_report(4, ""), Ok(None));
+}
+
+#[test]
+fn bad_levels_by_line_and_column() {
+    assert_eq!(parse_report(3, "1 2 x 4"), Err(error(3, 5, "x")));
+    assert_eq!(parse_report(4, "12 3.5"), Err(error(4, 4, "3.5")));
+    assert_eq!(
+        parse_report(5, "1 99999999999"),
+        Err(error(5, 3, "99999999999"))
+    );
+    // Columns count characters, so the two-byte `é` only moves the next token along by one.
+    assert_eq!(parse_report(6, "é 1 2é"), Err(error(6, 1, "é")));
+    assert_eq!(parse_report(7, "1\u{a0}é"), Err(error(7, 3, "é")));
+}
+
+const INPUT: &str = "7 6 4 2 1\n1 2 x 8 9\n\n9 7 6 2 1\n1 3 2 4 5y\n";
+
+#[test]
+fn parse_reports_stops_at_the_first_bad_level() {
+    assert_eq!(parse_reports(INPUT), Err(error(2, 5, "x")));
+    assert_eq!(
+        parse_reports("7 6\n\n9 7\n"),
+        Ok(vec![report(1, &[7, 6]), report(3, &[9, 7])])
+    );
+}
+
+#[test]
+fn skipping_sets_aside_bad_reports() {
+    let (reports, skipped) = parse_reports_skipping(INPUT);
+
+    assert_eq!(
+        reports,
+        [report(1, &[7, 6, 4, 2, 1]), report(4, &[9, 7, 6, 2, 1])]
+    );
+    assert_eq!(skipped, [error(2, 5, "x"), error(5, 9, "5y")]);
+    assert_eq!(
+        skipped[1].to_string(),
+        "line 5, column 9: `5y` is not a valid level"
+    );
+}
+
+#[test]
+fn skip_bad_flag() {
+    let options = Options::parse(["--skip-bad".to_string()]).unwrap();
+    let (reports, skipped) = options.read_reports(INPUT).unwrap();
+    assert_eq!(reports.len(), 2);
+    assert_eq!(skipped, [error(2, 5, "x"), error(5, 9, "5y")]);
+
+    let options = Options::parse([]).unwrap();
+    assert!(options.read_reports(INPUT).is_err());
+}
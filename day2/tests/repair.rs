@@ -0,0 +1,211 @@
+mod common;
+
+use std::collections::HashMap;
+
+use day2::{LevelEdit, Repair, SafetyPolicy};
+use serde_json::json;
+
+use common::XorShift;
+
+fn random_policy(rng: &mut XorShift) -> SafetyPolicy {
+    SafetyPolicy {
+        min_step: rng.below(3) as u32,
+        max_step: 1 + rng.below(3) as u32,
+        monotonic: rng.below(2) == 0,
+        allowed_plateaus: rng.below(3) as usize,
+    }
+}
+
+fn random_report(rng: &mut XorShift, max_len: u64) -> Vec<i32> {
+    (0..rng.below(max_len + 1))
+        .map(|_| rng.below(7) as i32)
+        .collect()
+}
+
+#[test]
+fn removable_levels_match_brute_force() {
+    let mut rng = XorShift(0x2024_0017);
+
+    for _ in 0..50_000 {
+        let policy = random_policy(&mut rng);
+        let report = random_report(&mut rng, 9);
+
+        let brute: Vec<usize> = (0..report.len())
+            .filter(|&index| {
+                let mut removed = report.clone();
+                removed.remove(index);
+                policy.is_safe(&removed)
+            })
+            .collect();
+
+        assert_eq!(
+            policy.removable_levels(&report),
+            brute,
+            "{policy:?} {report:?}"
+        );
+    }
+}
+
+fn apply(report: &[i32], edits: &[LevelEdit]) -> Vec<i32> {
+    let mut edited = report.to_vec();
+    edits.iter().for_each(|edit| {
+        assert_eq!(edit.from, report[edit.index]);
+        edited[edit.index] = edit.to.try_into().unwrap();
+    });
+    edited
+}
+
+/// Whether a step from `a` to `b` is allowed after `plateaus` plateaus, and how many it leaves
+/// used, written out from the policy's rules. `increasing` is `None` for a non-monotonic policy.
+fn step(
+    policy: &SafetyPolicy,
+    plateaus: usize,
+    a: i64,
+    b: i64,
+    increasing: Option<bool>,
+) -> Option<usize> {
+    let change = b - a;
+    if change == 0 && policy.min_step > 0 {
+        return (plateaus < policy.allowed_plateaus).then_some(plateaus + 1);
+    }
+
+    let in_direction = match increasing {
+        Some(true) => change >= 0,
+        Some(false) => change <= 0,
+        None => true,
+    };
+    let size = change.unsigned_abs();
+
+    (in_direction && size >= policy.min_step as u64 && size <= policy.max_step as u64)
+        .then_some(plateaus)
+}
+
+/// The fewest edits that make a report safe, by trying every value each level could take next
+/// to the one before it, or `None` if nothing works.
+fn min_edits_exhaustive(policy: &SafetyPolicy, report: &[i32]) -> Option<usize> {
+    fn search(
+        policy: &SafetyPolicy,
+        report: &[i32],
+        increasing: Option<bool>,
+        state: (usize, i64, usize),
+        memo: &mut HashMap<(usize, i64, usize), Option<usize>>,
+    ) -> Option<usize> {
+        let (index, previous, plateaus) = state;
+        if index == report.len() {
+            return Some(0);
+        }
+        if let Some(&known) = memo.get(&state) {
+            return known;
+        }
+
+        let reach = policy.max_step as i64;
+        let best = (previous - reach..=previous + reach)
+            .filter_map(|level| {
+                let plateaus = step(policy, plateaus, previous, level, increasing)?;
+                let rest = search(
+                    policy,
+                    report,
+                    increasing,
+                    (index + 1, level, plateaus),
+                    memo,
+                )?;
+                Some(rest + usize::from(level != report[index] as i64))
+            })
+            .min();
+
+        memo.insert(state, best);
+        best
+    }
+
+    if report.is_empty() {
+        return Some(0);
+    }
+
+    // The first level never needs to be further from the others than every step put together.
+    let reach = policy.max_step as i64 * report.len() as i64;
+    let directions: &[Option<bool>] = if policy.monotonic {
+        &[Some(true), Some(false)]
+    } else {
+        &[None]
+    };
+
+    directions
+        .iter()
+        .flat_map(|&increasing| {
+            let mut memo = HashMap::new();
+            (-reach..=6 + reach)
+                .filter_map(|first| {
+                    let rest = search(policy, report, increasing, (1, first, 0), &mut memo)?;
+                    Some(rest + usize::from(first != report[0] as i64))
+                })
+                .collect::<Vec<_>>()
+        })
+        .min()
+}
+
+#[test]
+fn min_edits_are_safe_and_minimal() {
+    let mut rng = XorShift(0x2024_0117);
+
+    for _ in 0..3_000 {
+        let policy = random_policy(&mut rng);
+        let report = random_report(&mut rng, 6);
+
+        let edits = policy.min_edits(&report);
+        if let Some(edits) = &edits {
+            let edited = apply(&report, edits);
+            assert!(
+                policy.is_safe(&edited),
+                "{policy:?} {report:?} edited to {edited:?}"
+            );
+        }
+
+        assert_eq!(
+            edits.map(|edits| edits.len()),
+            min_edits_exhaustive(&policy, &report),
+            "{policy:?} {report:?}"
+        );
+    }
+}
+
+#[test]
+fn example_repairs() {
+    let policy = SafetyPolicy::default();
+
+    assert_eq!(policy.removable_levels(&[1, 3, 2, 4, 5]), [1, 2]);
+    assert!(policy.removable_levels(&[1, 2, 7, 8, 9]).is_empty());
+
+    let report = [1, 2, 7, 8, 9];
+    let edits = policy.min_edits(&report).unwrap();
+    assert_eq!(edits.len(), 1);
+    assert!(policy.is_safe(&apply(&report, &edits)));
+}
+
+#[test]
+fn unrepairable_reports_say_so() {
+    // Only plateaus are allowed, and none of them, so no report of more than one level is safe.
+    let policy = SafetyPolicy {
+        min_step: 3,
+        max_step: 1,
+        monotonic: true,
+        allowed_plateaus: 0,
+    };
+
+    let repair = policy.repair(&[1, 2, 3]);
+    assert_eq!(
+        repair,
+        Repair {
+            removable: Vec::new(),
+            edits: None,
+            repairable: false,
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(&repair).unwrap(),
+        json!({ "removable": [], "repairable": false })
+    );
+
+    let repair = SafetyPolicy::default().repair(&[1, 2, 7, 8, 9]);
+    assert!(repair.repairable);
+    assert_eq!(repair.edits.map(|edits| edits.len()), Some(1));
+}
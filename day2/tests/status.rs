@@ -0,0 +1,190 @@
+mod common;
+
+use day2::{parse_reports, write_statuses, OutputFormat, ReportStatus, SafetyPolicy, UnsafeReason};
+use serde_json::json;
+
+use common::XorShift;
+
+const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+fn unsafe_at(index: usize, reason: UnsafeReason) -> ReportStatus {
+    ReportStatus::Unsafe { index, reason }
+}
+
+fn example_statuses(dampened: bool) -> Vec<ReportStatus> {
+    parse_reports(EXAMPLE)
+        .unwrap()
+        .iter()
+        .map(|report| SafetyPolicy::default().status(&report.levels, dampened))
+        .collect()
+}
+
+#[test]
+fn example_statuses_undampened() {
+    assert_eq!(
+        example_statuses(false),
+        [
+            ReportStatus::Safe,
+            unsafe_at(2, UnsafeReason::StepTooLarge),
+            unsafe_at(3, UnsafeReason::StepTooLarge),
+            unsafe_at(2, UnsafeReason::DirectionFlip),
+            unsafe_at(3, UnsafeReason::ZeroStep),
+            ReportStatus::Safe,
+        ]
+    );
+}
+
+#[test]
+fn example_statuses_dampened() {
+    assert_eq!(
+        example_statuses(true),
+        [
+            ReportStatus::Safe,
+            unsafe_at(2, UnsafeReason::StepTooLarge),
+            unsafe_at(3, UnsafeReason::StepTooLarge),
+            ReportStatus::SafeAfterRemoving(1),
+            ReportStatus::SafeAfterRemoving(2),
+            ReportStatus::Safe,
+        ]
+    );
+}
+
+#[test]
+fn unsafe_reasons() {
+    let policy = SafetyPolicy::default();
+
+    assert_eq!(
+        policy.status(&[5, 5, 6], false),
+        unsafe_at(1, UnsafeReason::ZeroStep)
+    );
+    // The direction comes from the first change, not from the first pair.
+    assert_eq!(
+        policy.status(&[5, 5, 6, 5], false),
+        unsafe_at(1, UnsafeReason::ZeroStep)
+    );
+    assert_eq!(
+        policy.status(&[1, 2, 3, 2], false),
+        unsafe_at(3, UnsafeReason::DirectionFlip)
+    );
+    assert_eq!(
+        policy.status(&[9, 8, 2], false),
+        unsafe_at(2, UnsafeReason::StepTooLarge)
+    );
+
+    let policy = SafetyPolicy {
+        min_step: 2,
+        allowed_plateaus: 1,
+        ..SafetyPolicy::default()
+    };
+    assert_eq!(
+        policy.status(&[1, 3, 4], false),
+        unsafe_at(2, UnsafeReason::StepTooSmall)
+    );
+    assert_eq!(policy.status(&[1, 3, 3, 5], false), ReportStatus::Safe);
+    assert_eq!(
+        policy.status(&[1, 3, 3, 5, 5], false),
+        unsafe_at(4, UnsafeReason::ZeroStep)
+    );
+}
+
+#[test]
+fn removed_index_is_the_first_that_works() {
+    let mut rng = XorShift(0x2024_0015);
+    let policy = SafetyPolicy::default();
+
+    for _ in 0..20_000 {
+        let mut level = rng.below(20) as i32;
+        let report: Vec<i32> = (0..rng.below(9))
+            .map(|_| {
+                level += rng.below(9) as i32 - 4;
+                level
+            })
+            .collect();
+
+        let first = (0..report.len()).find(|&index| {
+            let mut removed = report.clone();
+            removed.remove(index);
+            policy.is_safe(&removed)
+        });
+
+        match policy.status(&report, true) {
+            ReportStatus::Safe => assert!(policy.is_safe(&report), "{report:?}"),
+            ReportStatus::SafeAfterRemoving(index) => {
+                assert!(!policy.is_safe(&report), "{report:?}");
+                assert_eq!(Some(index), first, "{report:?}");
+            }
+            ReportStatus::Unsafe { .. } => assert_eq!(first, None, "{report:?}"),
+        }
+    }
+}
+
+fn output(format: OutputFormat) -> String {
+    let reports = parse_reports(EXAMPLE).unwrap();
+    let mut output = Vec::new();
+    write_statuses(&mut output, &reports, &example_statuses(true), format).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn example_as_count() {
+    assert_eq!(output(OutputFormat::Count), "4\n");
+}
+
+#[test]
+fn example_as_table() {
+    assert_eq!(
+        output(OutputFormat::Table),
+        "\
+Line  Levels     Status
+   1  7 6 4 2 1  safe
+   2  1 2 7 8 9  unsafe: step too large at index 2
+   3  9 7 6 2 1  unsafe: step too large at index 3
+   4  1 3 2 4 5  safe after removing index 1
+   5  8 6 4 4 1  safe after removing index 2
+   6  1 3 6 7 9  safe
+Safe: 4
+"
+    );
+}
+
+#[test]
+fn example_as_json() {
+    let json: serde_json::Value = serde_json::from_str(&output(OutputFormat::Json)).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "reports": [
+                {"line": 1, "levels": [7, 6, 4, 2, 1], "status": "safe"},
+                {
+                    "line": 2,
+                    "levels": [1, 2, 7, 8, 9],
+                    "status": "unsafe",
+                    "index": 2,
+                    "reason": "step_too_large"
+                },
+                {
+                    "line": 3,
+                    "levels": [9, 7, 6, 2, 1],
+                    "status": "unsafe",
+                    "index": 3,
+                    "reason": "step_too_large"
+                },
+                {
+                    "line": 4,
+                    "levels": [1, 3, 2, 4, 5],
+                    "status": "safe_after_removing",
+                    "removed_index": 1
+                },
+                {
+                    "line": 5,
+                    "levels": [8, 6, 4, 4, 1],
+                    "status": "safe_after_removing",
+                    "removed_index": 2
+                },
+                {"line": 6, "levels": [1, 3, 6, 7, 9], "status": "safe"}
+            ],
+            "safe": 4
+        })
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use day2::{parse_reports_skipping, SafetyPolicy, StreamEval};
+
+use common::XorShift;
+
+/// Random reports, with the odd blank line and bad level mixed in.
+fn random_input(rng: &mut XorShift, lines: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..lines {
+        let mut level = rng.below(20) as i32;
+        for _ in 0..rng.below(9) {
+            level += rng.below(9) as i32 - 4;
+            input.push_str(&format!("{level} "));
+        }
+        if rng.below(50) == 0 {
+            input.push_str("x1");
+        }
+        input.push('\n');
+    }
+
+    input
+}
+
+#[test]
+fn matches_in_memory_counts_at_any_batch_size() {
+    let mut rng = XorShift(0x2024_0018);
+    let input = random_input(&mut rng, 5_000);
+    let policy = SafetyPolicy::default();
+
+    let (reports, skipped) = parse_reports_skipping(&input);
+    let safe = reports
+        .iter()
+        .filter(|report| policy.is_safe(&report.levels))
+        .count();
+    let safe_dampened = reports
+        .iter()
+        .filter(|report| policy.is_safe_dampened(&report.levels))
+        .count();
+
+    for batch_size in [1, 7, 64, 4_999, 5_000, 100_000] {
+        let totals = StreamEval { batch_size }
+            .totals(input.as_bytes(), &policy, true)
+            .unwrap();
+
+        assert_eq!(totals.reports, reports.len(), "batch size {batch_size}");
+        assert_eq!(totals.safe, safe, "batch size {batch_size}");
+        assert_eq!(
+            totals.safe_dampened, safe_dampened,
+            "batch size {batch_size}"
+        );
+        assert_eq!(totals.skipped, skipped, "batch size {batch_size}");
+    }
+}
+
+#[test]
+fn fails_on_the_first_bad_report_without_skip_bad() {
+    let input = "1 2 3\n4 x5 6\n7 8 y9\n";
+
+    let error = StreamEval { batch_size: 1 }
+        .totals(input.as_bytes(), &SafetyPolicy::default(), false)
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 3: `x5` is not a valid level"
+    );
+}
@@ -0,0 +1,61 @@
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::{hint::black_box, time::Instant};
+
+use day3::{regex_instructions, Grammar, Instruction, Lexer};
+
+use common::XorShift;
+
+// Compares the regex scan day3 has always used with the hand-written lexer.
+// Run with `cargo bench --bench lexer`.
+
+/// Corrupted memory in the style of the puzzle input: real instructions mixed in with junk and
+/// instructions that are almost right.
+fn corrupted_memory(rng: &mut XorShift, len: usize) -> String {
+    const JUNK: &[u8] = b"mul(,)don't'do[]{}<>!@#$%^&*+-_ ?:;~0123456789\n";
+
+    let mut memory = String::with_capacity(len);
+    while memory.len() < len {
+        match rng.below(12) {
+            0 => memory.push_str(&format!("mul({},{})", rng.below(1000), rng.below(1000))),
+            1 => memory.push_str("do()"),
+            2 => memory.push_str("don't()"),
+            3 => memory.push_str(&format!("mul({} ,{}]", rng.below(1000), rng.below(1000))),
+            _ => memory.push(JUNK[rng.below(JUNK.len() as u64) as usize] as char),
+        }
+    }
+
+    memory
+}
+
+fn bench(name: &str, memory: &str) {
+    const ROUNDS: u32 = 3;
+
+    let time = |scan: &dyn Fn(&str) -> Vec<Instruction>| {
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            black_box(scan(black_box(memory)));
+        }
+        start.elapsed() / ROUNDS
+    };
+
+    let regex = time(&|memory| regex_instructions(memory, &Grammar::default()));
+    let lexer = time(&|memory| Lexer::new(memory).collect());
+
+    assert_eq!(
+        regex_instructions(memory, &Grammar::default()),
+        Lexer::new(memory).collect::<Vec<_>>(),
+        "{name}: the lexer found different instructions"
+    );
+
+    println!("{name:<20} regex: {regex:>12.2?}   lexer: {lexer:>12.2?}");
+}
+
+fn main() {
+    let mut rng = XorShift(0x2024_0020);
+
+    for len in [10_000, 100_000, 1_000_000] {
+        bench(&format!("{len} bytes"), &corrupted_memory(&mut rng, len));
+    }
+}
@@ -0,0 +1,37 @@
+use std::{fs::File, io::Read};
+
+use anyhow::Result;
+use day3::Options;
+
+// Usage: diagnose [--instructions LIST] [--min-digits N] [--max-digits N] [--signed]
+// Lists everything that starts like an instruction but doesn't follow the grammar, and why.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_extra()?;
+    options.expect_no_regex()?;
+
+    let instructions = options.instruction_set()?;
+
+    let mut file = File::open("./input.txt")?;
+    let mut data = String::new();
+
+    let _ = file.read_to_string(&mut data)?;
+
+    let mut near_misses = 0;
+    for near_miss in instructions
+        .scan_with_near_misses(&data)
+        .filter_map(Result::err)
+    {
+        near_misses += 1;
+        println!(
+            "Offset {}: `{}`: {}",
+            near_miss.span.start,
+            data[near_miss.span.clone()].escape_debug(),
+            near_miss.reason
+        );
+    }
+
+    println!("Near misses: {near_misses}");
+
+    Ok(())
+}
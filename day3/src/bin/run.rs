@@ -0,0 +1,27 @@
+use std::{fs::File, io::Read};
+
+use anyhow::Result;
+use day3::Options;
+
+// Usage: run [--instructions mul,do,don't,add,sub,div,mod,neg] [--min-digits N]
+//            [--max-digits N] [--signed]
+// Runs the memory through the interpreter with the chosen instructions, `mul`, `do` and `don't`
+// by default, and prints the sum.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_extra()?;
+    options.expect_no_regex()?;
+
+    let instructions = options.instruction_set()?;
+
+    let mut file = File::open("./input.txt")?;
+    let mut data = String::new();
+
+    let _ = file.read_to_string(&mut data)?;
+
+    let result = instructions.run(&data)?;
+
+    println!("Result: {result}");
+
+    Ok(())
+}
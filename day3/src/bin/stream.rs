@@ -0,0 +1,45 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+};
+
+use anyhow::{anyhow, Result};
+use day3::{stream_sums, Options};
+
+// Usage: stream [--chunk-size N] [--min-digits N] [--max-digits N] [PATH]
+// Reads the memory a chunk at a time, from stdin when no path (or `-`) is given, and prints both
+// parts' results.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_regex()?;
+    options.expect_puzzle_instructions()?;
+
+    let mut chunk_size = 64 * 1024;
+    let mut path = None;
+
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--chunk-size" => {
+                chunk_size = args
+                    .next()
+                    .ok_or(anyhow!("--chunk-size needs a number"))?
+                    .parse()?
+            }
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let input: Box<dyn Read> = match path.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(File::open(path)?),
+    };
+
+    let sums = stream_sums(input, chunk_size, options.grammar)?;
+
+    println!("Part 1: {}", sums.all);
+    println!("Part 2: {}", sums.enabled);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+};
+
+use anyhow::{anyhow, Result};
+use day3::{trace, write_trace, Options, TraceFormat};
+
+// Usage: trace [--format text|json] [--instructions LIST] [--min-digits N] [--max-digits N]
+//              [--signed]
+// Lists every recognised instruction with where it is, whether it was enabled and what it added
+// to the sum.
+fn main() -> Result<()> {
+    let options = Options::from_env()?;
+    options.expect_no_regex()?;
+
+    let mut format = TraceFormat::default();
+    let mut args = options.extra.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = args
+                    .next()
+                    .ok_or(anyhow!("--format needs a value"))?
+                    .parse()
+                    .map_err(|e: String| anyhow!(e))?
+            }
+            _ => return Err(anyhow!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let instructions = options.instruction_set()?;
+
+    let mut file = File::open("./input.txt")?;
+    let mut data = String::new();
+
+    let _ = file.read_to_string(&mut data)?;
+
+    let entries = trace(&instructions, &data)?;
+    write_trace(io::stdout().lock(), &entries, format)?;
+
+    Ok(())
+}
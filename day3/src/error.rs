@@ -0,0 +1,101 @@
+use std::{fmt, ops::Range};
+
+/// One of a multiplication's two operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    X,
+    Y,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::X => write!(f, "x"),
+            Self::Y => write!(f, "y"),
+        }
+    }
+}
+
+/// Why text couldn't be read as an [`crate::Instruction`].
+///
+/// Each variant carries the span of the text at fault, in bytes. From `try_from` it's relative
+/// to the string that was parsed; from [`crate::regex_instructions_checked`] it's relative to the
+/// whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionError {
+    /// The operand is left out altogether, with an empty span where it should have been.
+    MissingOperand {
+        operand: Operand,
+        span: Range<usize>,
+    },
+    /// There's text where the operand should be, but it isn't a number, e.g. the `2,3` of
+    /// `mul(1,2,3)`.
+    InvalidOperand {
+        operand: Operand,
+        span: Range<usize>,
+    },
+    /// The operand doesn't fit in a `u16`.
+    Overflow {
+        operand: Operand,
+        span: Range<usize>,
+    },
+    /// The text isn't `mul(x,y)`, `do()` or `don't()`.
+    UnknownInstruction { span: Range<usize> },
+}
+
+impl InstructionError {
+    pub fn span(&self) -> &Range<usize> {
+        match self {
+            Self::MissingOperand { span, .. }
+            | Self::InvalidOperand { span, .. }
+            | Self::Overflow { span, .. }
+            | Self::UnknownInstruction { span } => span,
+        }
+    }
+
+    /// Moves the span along by `offset` bytes, for text that was parsed out of a larger input.
+    pub fn offset_by(mut self, offset: usize) -> Self {
+        match &mut self {
+            Self::MissingOperand { span, .. }
+            | Self::InvalidOperand { span, .. }
+            | Self::Overflow { span, .. }
+            | Self::UnknownInstruction { span } => {
+                *span = span.start + offset..span.end + offset;
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        match self {
+            Self::MissingOperand { operand, .. } => {
+                write!(f, "bytes {span:?}: missing operand {operand}")
+            }
+            Self::InvalidOperand { operand, .. } => {
+                write!(f, "bytes {span:?}: operand {operand} isn't a number")
+            }
+            Self::Overflow { operand, .. } => {
+                write!(f, "bytes {span:?}: operand {operand} doesn't fit in a u16")
+            }
+            Self::UnknownInstruction { .. } => write!(f, "bytes {span:?}: unknown instruction"),
+        }
+    }
+}
+
+impl std::error::Error for InstructionError {}
+
+/// Returned when a running sum gets too big: for a `u64` of the puzzle's multiplications, which
+/// takes billions of them, or for the interpreter's `i64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SumOverflow;
+
+impl fmt::Display for SumOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the sum is too big to hold")
+    }
+}
+
+impl std::error::Error for SumOverflow {}
@@ -0,0 +1,231 @@
+use std::{fmt, ops::Range};
+
+use crate::{Call, Operation};
+
+/// What counts as an operand.
+///
+/// The default is the puzzle's: one to three digits, with no sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grammar {
+    pub min_digits: usize,
+    pub max_digits: usize,
+    /// Whether an operand may start with `-`.
+    pub signed: bool,
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        Self {
+            min_digits: 1,
+            max_digits: 3,
+            signed: false,
+        }
+    }
+}
+
+impl Grammar {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_digits == 0 {
+            Err("Operands need at least one digit".to_string())
+        } else if self.min_digits > self.max_digits {
+            Err(format!(
+                "At least {} digits but at most {} can't both hold",
+                self.min_digits, self.max_digits
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Why something that started like an instruction wasn't one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// `found` is `None` at the end of the input.
+    Expected {
+        expected: &'static str,
+        found: Option<char>,
+    },
+    /// A `-` on an operand when the grammar is unsigned. `operand` is 1-based, as are the
+    /// others.
+    Signed { operand: usize },
+    TooFewDigits {
+        operand: usize,
+        digits: usize,
+        min: usize,
+    },
+    TooManyDigits {
+        operand: usize,
+        digits: usize,
+        max: usize,
+    },
+    /// The operand has few enough digits but still doesn't fit in an `i64`.
+    Overflow { operand: usize },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Expected {
+                expected,
+                found: Some(found),
+            } => write!(f, "expected {expected}, found `{found}`"),
+            Self::Expected {
+                expected,
+                found: None,
+            } => write!(f, "expected {expected}, found the end of the input"),
+            Self::Signed { operand } => {
+                write!(f, "operand {operand} has a sign, but operands are unsigned")
+            }
+            Self::TooFewDigits {
+                operand,
+                digits,
+                min,
+            } => write!(
+                f,
+                "operand {operand} has {digits} digit(s), at least {min} needed"
+            ),
+            Self::TooManyDigits {
+                operand,
+                digits,
+                max,
+            } => write!(
+                f,
+                "operand {operand} has {digits} digits, at most {max} allowed"
+            ),
+            Self::Overflow { operand } => write!(f, "operand {operand} is too big"),
+        }
+    }
+}
+
+/// Text that starts like a call, a known name and `(`, but isn't a valid one.
+#[derive(Clone)]
+pub struct NearMiss<'a> {
+    pub operation: &'a dyn Operation,
+    /// From the start of the name to just past where it went wrong, in bytes.
+    pub span: Range<usize>,
+    pub reason: Rejection,
+}
+
+/// Reads a call to `operation` starting at `start`. Fails with `None` if the input there doesn't
+/// even start with the name and `(`.
+pub(crate) fn parse_call<'a>(
+    operation: &'a dyn Operation,
+    grammar: &Grammar,
+    input: &str,
+    start: usize,
+) -> Result<Call<'a>, Option<NearMiss<'a>>> {
+    let mut parser = Parser {
+        input,
+        position: start,
+    };
+
+    if !parser.eat(operation.name()) || !parser.eat("(") {
+        return Err(None);
+    }
+
+    let near_miss = |parser: &Parser, reason| {
+        Some(NearMiss {
+            operation,
+            span: start..parser.position,
+            reason,
+        })
+    };
+
+    let mut operands = Vec::with_capacity(operation.arity());
+    for index in 0..operation.arity() {
+        if index > 0 {
+            parser
+                .expect(",", "`,`")
+                .map_err(|reason| near_miss(&parser, reason))?;
+        }
+
+        let operand = parser
+            .operand(grammar, index + 1)
+            .map_err(|reason| near_miss(&parser, reason))?;
+        operands.push(operand);
+    }
+
+    parser
+        .expect(")", "`)`")
+        .map_err(|reason| near_miss(&parser, reason))?;
+
+    Ok(Call {
+        operation,
+        operands,
+        span: start..parser.position,
+    })
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    /// Moves past `expected` if it comes next.
+    fn eat(&mut self, expected: &str) -> bool {
+        let found = self.input[self.position..].starts_with(expected);
+        if found {
+            self.position += expected.len();
+        }
+        found
+    }
+
+    /// Moves past `token`, which is described as `expected` if it isn't there.
+    fn expect(&mut self, token: &str, expected: &'static str) -> Result<(), Rejection> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.unexpected(expected)),
+        }
+    }
+
+    /// Moves past the character that isn't `expected`, so it's part of the near miss.
+    fn unexpected(&mut self, expected: &'static str) -> Rejection {
+        let found = self.peek();
+        self.position += found.map_or(0, char::len_utf8);
+
+        Rejection::Expected { expected, found }
+    }
+
+    /// Reads an operand. On failure, `position` is left just past the part that's wrong.
+    fn operand(&mut self, grammar: &Grammar, operand: usize) -> Result<i64, Rejection> {
+        let start = self.position;
+        if self.eat("-") && !grammar.signed {
+            return Err(Rejection::Signed { operand });
+        }
+
+        let digits = self.input[self.position..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+
+        if digits == 0 {
+            return Err(self.unexpected("a digit"));
+        }
+
+        self.position += digits;
+        if digits < grammar.min_digits {
+            return Err(Rejection::TooFewDigits {
+                operand,
+                digits,
+                min: grammar.min_digits,
+            });
+        }
+        if digits > grammar.max_digits {
+            return Err(Rejection::TooManyDigits {
+                operand,
+                digits,
+                max: grammar.max_digits,
+            });
+        }
+
+        self.input[start..self.position]
+            .parse()
+            .map_err(|_| Rejection::Overflow { operand })
+    }
+}
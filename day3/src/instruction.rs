@@ -0,0 +1,110 @@
+use crate::{InstructionError, Operand, SumOverflow};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Do,
+    Dont,
+    Multiplication(Multiplication),
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = InstructionError;
+
+    fn try_from(instruction_string: &str) -> std::result::Result<Self, Self::Error> {
+        match instruction_string {
+            "do()" => Ok(Self::Do),
+            "don't()" => Ok(Self::Dont),
+            _ => Ok(Self::Multiplication(Multiplication::try_from(
+                instruction_string,
+            )?)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Multiplication {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl TryFrom<&str> for Multiplication {
+    type Error = InstructionError;
+
+    /// Reads `mul(x,y)`.
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let operands = value
+            .strip_prefix("mul(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or(InstructionError::UnknownInstruction {
+                span: 0..value.len(),
+            })?;
+        let start = "mul(".len();
+
+        let (x, y) = match operands.split_once(',') {
+            Some((x, y)) => ((x, start), (y, start + x.len() + 1)),
+            // With no comma the whole thing is x, and y is missing from just before the `)`.
+            None => ((operands, start), ("", start + operands.len())),
+        };
+
+        Ok(Self {
+            x: parse_operand(Operand::X, x.0, x.1)?,
+            y: parse_operand(Operand::Y, y.0, y.1)?,
+        })
+    }
+}
+
+/// Reads an operand that starts `start` bytes into the instruction.
+fn parse_operand(operand: Operand, text: &str, start: usize) -> Result<u16, InstructionError> {
+    let span = start..start + text.len();
+
+    if text.is_empty() {
+        return Err(InstructionError::MissingOperand { operand, span });
+    }
+    if !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(InstructionError::InvalidOperand { operand, span });
+    }
+
+    text.parse()
+        .map_err(|_| InstructionError::Overflow { operand, span })
+}
+
+impl Multiplication {
+    pub fn multiply(self) -> u32 {
+        self.x as u32 * self.y as u32
+    }
+}
+
+/// Sums every multiplication, ignoring `do()` and `don't()`, for part1.
+pub fn sum_all(instructions: impl IntoIterator<Item = Instruction>) -> Result<u64, SumOverflow> {
+    instructions
+        .into_iter()
+        .try_fold(0u64, |sum, instruction| match instruction {
+            Instruction::Multiplication(multiplication) => sum
+                .checked_add(u64::from(multiplication.multiply()))
+                .ok_or(SumOverflow),
+            _ => Ok(sum),
+        })
+}
+
+/// Sums the multiplications that aren't switched off by a `don't()`, for part2.
+pub fn sum_enabled(
+    instructions: impl IntoIterator<Item = Instruction>,
+) -> Result<u64, SumOverflow> {
+    let mut do_mul = true;
+    instructions
+        .into_iter()
+        .try_fold(0u64, |sum, instruction| match instruction {
+            Instruction::Do => {
+                do_mul = true;
+                Ok(sum)
+            }
+            Instruction::Dont => {
+                do_mul = false;
+                Ok(sum)
+            }
+            Instruction::Multiplication(multiplication) if do_mul => sum
+                .checked_add(u64::from(multiplication.multiply()))
+                .ok_or(SumOverflow),
+            Instruction::Multiplication(_) => Ok(sum),
+        })
+}
@@ -0,0 +1,295 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{grammar::parse_call, Grammar, NearMiss, SumOverflow};
+
+/// What running an instruction does to the interpreter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Adds a value to the running sum, if instructions are enabled.
+    Contribute(i64),
+    Enable,
+    Disable,
+    /// Nothing at all, e.g. a division by zero or a result too big for an `i64`.
+    Skip,
+}
+
+/// An instruction the interpreter knows how to find and run.
+///
+/// Its grammar is its name followed by `arity` operands in parentheses, separated by commas,
+/// e.g. `mul(2,4)` or `do()`.
+pub trait Operation: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn arity(&self) -> usize;
+
+    /// The [`Grammar`] this instruction's operands follow, or `None` to use the instruction
+    /// set's.
+    fn grammar(&self) -> Option<Grammar> {
+        None
+    }
+
+    /// Runs the instruction on exactly `arity` operands.
+    fn run(&self, operands: &[i64]) -> Effect;
+}
+
+/// An instruction from a name, an arity and a plain function, which covers all the built-in
+/// ones.
+#[derive(Clone, Copy)]
+struct Builtin {
+    name: &'static str,
+    arity: usize,
+    run: fn(&[i64]) -> Effect,
+}
+
+impl Operation for Builtin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn run(&self, operands: &[i64]) -> Effect {
+        (self.run)(operands)
+    }
+}
+
+const BUILTINS: &[Builtin] = &[
+    Builtin {
+        name: "mul",
+        arity: 2,
+        run: |o| {
+            o[0].checked_mul(o[1])
+                .map_or(Effect::Skip, Effect::Contribute)
+        },
+    },
+    Builtin {
+        name: "do",
+        arity: 0,
+        run: |_| Effect::Enable,
+    },
+    Builtin {
+        name: "don't",
+        arity: 0,
+        run: |_| Effect::Disable,
+    },
+    Builtin {
+        name: "add",
+        arity: 2,
+        run: |o| {
+            o[0].checked_add(o[1])
+                .map_or(Effect::Skip, Effect::Contribute)
+        },
+    },
+    Builtin {
+        name: "sub",
+        arity: 2,
+        run: |o| {
+            o[0].checked_sub(o[1])
+                .map_or(Effect::Skip, Effect::Contribute)
+        },
+    },
+    Builtin {
+        name: "div",
+        arity: 2,
+        run: |o| {
+            o[0].checked_div(o[1])
+                .map_or(Effect::Skip, Effect::Contribute)
+        },
+    },
+    Builtin {
+        name: "mod",
+        arity: 2,
+        run: |o| {
+            o[0].checked_rem(o[1])
+                .map_or(Effect::Skip, Effect::Contribute)
+        },
+    },
+    Builtin {
+        name: "neg",
+        arity: 1,
+        run: |o| o[0].checked_neg().map_or(Effect::Skip, Effect::Contribute),
+    },
+];
+
+/// The instructions the puzzle itself uses.
+pub const PUZZLE_INSTRUCTIONS: &[&str] = &["mul", "do", "don't"];
+
+/// A recognised instruction in the input.
+#[derive(Clone)]
+pub struct Call<'a> {
+    pub operation: &'a dyn Operation,
+    pub operands: Vec<i64>,
+    /// Where the instruction is in the input, in bytes.
+    pub span: Range<usize>,
+}
+
+impl Call<'_> {
+    pub fn run(&self) -> Effect {
+        self.operation.run(&self.operands)
+    }
+}
+
+/// The instructions an interpreter will recognise, and the [`Grammar`] their operands follow
+/// unless an instruction has its own; anything else in the input is skipped as corrupted memory.
+#[derive(Clone, Default)]
+pub struct InstructionSet {
+    operations: Vec<Arc<dyn Operation>>,
+    grammar: Grammar,
+}
+
+impl InstructionSet {
+    /// Every built-in instruction: `mul`, `do`, `don't`, `add`, `sub`, `div`, `mod` and `neg`.
+    pub fn builtin() -> Self {
+        let mut set = Self::default();
+        for builtin in BUILTINS {
+            set.register(*builtin);
+        }
+        set
+    }
+
+    /// Just `mul`, `do` and `don't`, as in part2.
+    pub fn puzzle() -> Self {
+        Self::builtin().select(PUZZLE_INSTRUCTIONS).unwrap()
+    }
+
+    /// Adds an instruction, replacing any already registered under the same name.
+    pub fn register(&mut self, operation: impl Operation + 'static) {
+        self.operations
+            .retain(|known| known.name() != operation.name());
+        self.operations.push(Arc::new(operation));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Operation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.name() == name)
+            .map(|operation| operation.as_ref())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.operations.iter().map(|operation| operation.name())
+    }
+
+    /// Only the named instructions, failing on a name that isn't registered.
+    pub fn select(&self, names: &[impl AsRef<str>]) -> Result<Self, String> {
+        let operations = names
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                self.operations
+                    .iter()
+                    .find(|operation| operation.name() == name)
+                    .cloned()
+                    .ok_or_else(|| {
+                        let known: Vec<&str> = self.names().collect();
+                        format!(
+                            "Unknown instruction `{name}`, expected one of {}",
+                            known.join(", ")
+                        )
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            operations,
+            grammar: self.grammar,
+        })
+    }
+
+    pub fn with_grammar(self, grammar: Grammar) -> Self {
+        Self { grammar, ..self }
+    }
+
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Every instruction in the input, in order.
+    pub fn scan<'a>(&'a self, input: &'a str) -> impl Iterator<Item = Call<'a>> + 'a {
+        self.scan_with_near_misses(input).filter_map(Result::ok)
+    }
+
+    /// Every instruction in the input, along with anything that starts like one (a known name
+    /// and `(`) but doesn't follow the grammar, in order.
+    pub fn scan_with_near_misses<'a>(
+        &'a self,
+        input: &'a str,
+    ) -> impl Iterator<Item = Result<Call<'a>, NearMiss<'a>>> + 'a {
+        let mut position = 0;
+
+        std::iter::from_fn(move || {
+            while let Some(char) = input[position..].chars().next() {
+                let start = position;
+                position += char.len_utf8();
+
+                let mut near_miss = None;
+                for operation in &self.operations {
+                    let grammar = operation.grammar().unwrap_or(self.grammar);
+                    match parse_call(operation.as_ref(), &grammar, input, start) {
+                        Ok(call) => {
+                            position = call.span.end;
+                            return Some(Ok(call));
+                        }
+                        Err(miss) => near_miss = near_miss.or(miss),
+                    }
+                }
+
+                if let Some(near_miss) = near_miss {
+                    return Some(Err(near_miss));
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Runs every instruction in the input, returning the sum, or an error if it gets too big
+    /// for an `i64`.
+    pub fn run(&self, input: &str) -> Result<i64, SumOverflow> {
+        let mut interpreter = Interpreter::default();
+        for call in self.scan(input) {
+            interpreter.execute(&call)?;
+        }
+        Ok(interpreter.sum)
+    }
+}
+
+/// The running state of a program: whether instructions are enabled, and the sum so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interpreter {
+    pub enabled: bool,
+    pub sum: i64,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sum: 0,
+        }
+    }
+}
+
+impl Interpreter {
+    /// Runs one instruction, returning how much it added to the sum. An instruction that would
+    /// take the sum past what an `i64` holds fails, and leaves the sum as it was.
+    pub fn execute(&mut self, call: &Call) -> Result<i64, SumOverflow> {
+        match call.run() {
+            Effect::Contribute(value) if self.enabled => {
+                self.sum = self.sum.checked_add(value).ok_or(SumOverflow)?;
+                Ok(value)
+            }
+            Effect::Enable => {
+                self.enabled = true;
+                Ok(0)
+            }
+            Effect::Disable => {
+                self.enabled = false;
+                Ok(0)
+            }
+            Effect::Contribute(_) | Effect::Skip => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{Grammar, Instruction, Multiplication};
+
+/// Operands this big no longer fit in a `u16`, so the multiplication is dropped.
+const TOO_BIG: u32 = u16::MAX as u32 + 1;
+
+/// How much of an instruction has been read so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Start,
+    M,
+    Mu,
+    Mul,
+    /// Read `mul(`.
+    MulOpen,
+    /// Read `mul(` and some of x: its value so far and how many digits it has.
+    X(u32, usize),
+    /// Read `mul(x,`.
+    Comma(u32),
+    /// Read `mul(x,` and some of y.
+    Y(u32, u32, usize),
+    D,
+    Do,
+    /// Read `do(`.
+    DoOpen,
+    Don,
+    /// Read `don'`.
+    DonQuote,
+    Dont,
+    /// Read `don't(`.
+    DontOpen,
+}
+
+/// The scanner's state machine, fed one byte at a time.
+///
+/// No instruction contains an `m` or `d` after its first byte, so when a byte doesn't fit the
+/// instruction being read, the only place a new one can start is that same byte.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Machine {
+    state: State,
+    grammar: Grammar,
+}
+
+impl Machine {
+    /// Only `grammar`'s digit counts apply, since multiplications are always unsigned.
+    pub(crate) fn new(grammar: Grammar) -> Self {
+        Self {
+            state: State::Start,
+            grammar,
+        }
+    }
+
+    /// Moves on by one byte, returning the instruction it finished, if any.
+    pub(crate) fn feed(&mut self, byte: u8) -> Option<Instruction> {
+        match self.step(byte) {
+            Some(next) => {
+                self.state = next;
+                None
+            }
+            None if self.state == State::Start => None,
+            None => self.finish_or_restart(byte),
+        }
+    }
+
+    /// The state after `byte`, or `None` if it doesn't continue the current instruction.
+    fn step(&self, byte: u8) -> Option<State> {
+        use State::*;
+
+        let Grammar {
+            min_digits,
+            max_digits,
+            ..
+        } = self.grammar;
+
+        Some(match (self.state, byte) {
+            (Start, b'm') => M,
+            (Start, b'd') => D,
+            (Start, _) => Start,
+            (M, b'u') => Mu,
+            (Mu, b'l') => Mul,
+            (Mul, b'(') => MulOpen,
+            (MulOpen, b'0'..=b'9') => X(digit(0, byte), 1),
+            (X(x, digits), b'0'..=b'9') if digits < max_digits => X(digit(x, byte), digits + 1),
+            (X(x, digits), b',') if digits >= min_digits => Comma(x),
+            (Comma(x), b'0'..=b'9') => Y(x, digit(0, byte), 1),
+            (Y(x, y, digits), b'0'..=b'9') if digits < max_digits => {
+                Y(x, digit(y, byte), digits + 1)
+            }
+            (D, b'o') => Do,
+            (Do, b'(') => DoOpen,
+            (Do, b'n') => Don,
+            (Don, b'\'') => DonQuote,
+            (DonQuote, b't') => Dont,
+            (Dont, b'(') => DontOpen,
+            _ => return None,
+        })
+    }
+
+    /// Handles a byte that doesn't continue the current instruction: either it closes one, or
+    /// the partial instruction is abandoned and the byte is read again from the start.
+    fn finish_or_restart(&mut self, byte: u8) -> Option<Instruction> {
+        let state = std::mem::take(&mut self.state);
+
+        match (state, byte) {
+            (State::Y(x, y, digits), b')') if digits >= self.grammar.min_digits => (x < TOO_BIG
+                && y < TOO_BIG)
+                .then_some(Instruction::Multiplication(Multiplication {
+                    x: x as u16,
+                    y: y as u16,
+                })),
+            (State::DoOpen, b')') => Some(Instruction::Do),
+            (State::DontOpen, b')') => Some(Instruction::Dont),
+            _ => self.feed(byte),
+        }
+    }
+}
+
+/// Adds a digit to an operand, stopping once it's too big to matter.
+fn digit(value: u32, byte: u8) -> u32 {
+    (value * 10 + u32::from(byte - b'0')).min(TOO_BIG)
+}
+
+/// Finds `mul(x,y)`, `do()` and `don't()` instructions in one pass over the input, without
+/// allocating.
+///
+/// Operands have as many digits as the [`Grammar`] allows, one to three by default as in the
+/// puzzle; its `signed` flag is ignored, since multiplications are always unsigned. Gives the
+/// same instructions as [`crate::regex_instructions`] under the same grammar, including
+/// dropping multiplications whose operands don't fit in a `u16`.
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    input: &'a [u8],
+    position: usize,
+    machine: Machine,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::with_grammar(input, Grammar::default())
+    }
+
+    pub fn with_grammar(input: &'a str, grammar: Grammar) -> Self {
+        Self {
+            input: input.as_bytes(),
+            position: 0,
+            machine: Machine::new(grammar),
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Instruction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&byte) = self.input.get(self.position) {
+            self.position += 1;
+            if let Some(instruction) = self.machine.feed(byte) {
+                return Some(instruction);
+            }
+        }
+
+        None
+    }
+}
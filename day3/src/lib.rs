@@ -0,0 +1,19 @@
+pub use error::{InstructionError, Operand, SumOverflow};
+pub use grammar::{Grammar, NearMiss, Rejection};
+pub use instruction::{sum_all, sum_enabled, Instruction, Multiplication};
+pub use interpreter::{Call, Effect, InstructionSet, Interpreter, Operation, PUZZLE_INSTRUCTIONS};
+pub use lexer::Lexer;
+pub use options::Options;
+pub use regex_scan::{regex_instructions, regex_instructions_checked};
+pub use stream::{stream_sums, ChunkedLexer, Sums};
+pub use trace::{trace, write_trace, TraceEntry, TraceFormat};
+
+mod error;
+mod grammar;
+mod instruction;
+mod interpreter;
+mod lexer;
+mod options;
+mod regex_scan;
+mod stream;
+mod trace;
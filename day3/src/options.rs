@@ -0,0 +1,144 @@
+use anyhow::{anyhow, Result};
+
+use crate::{
+    regex_instructions_checked, Grammar, Instruction, InstructionError, InstructionSet, Lexer,
+};
+
+/// The most digits an operand can have and always fit in a `u16`.
+const MAX_PUZZLE_DIGITS: usize = 4;
+
+/// Command line flags shared by the day3 binaries.
+///
+/// `--regex` finds instructions with the original regexes instead of the [`crate::Lexer`].
+/// `--instructions LIST` picks which instructions the interpreter recognises, as a
+/// comma-separated list of names such as `mul,do,don't,add`, and `--min-digits N`,
+/// `--max-digits N` and `--signed` set the [`Grammar`] their operands follow. The digit counts
+/// apply to the lexer and the regexes too, up to four digits, but `--instructions` and `--signed`
+/// only to the interpreter.
+/// Flags a binary handles itself are left in `extra`, in the order they were given.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub regex: bool,
+    /// Empty for the puzzle's own instructions.
+    pub instructions: Vec<String>,
+    pub grammar: Grammar,
+    pub extra: Vec<String>,
+}
+
+impl Options {
+    pub fn from_env() -> Result<Self> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--regex" => options.regex = true,
+                "--instructions" => {
+                    options.instructions = args
+                        .next()
+                        .ok_or(anyhow!("--instructions needs a list of names"))?
+                        .split(',')
+                        .map(str::to_string)
+                        .collect()
+                }
+                "--min-digits" => {
+                    options.grammar.min_digits = args
+                        .next()
+                        .ok_or(anyhow!("--min-digits needs a number"))?
+                        .parse()?
+                }
+                "--max-digits" => {
+                    options.grammar.max_digits = args
+                        .next()
+                        .ok_or(anyhow!("--max-digits needs a number"))?
+                        .parse()?
+                }
+                "--signed" => options.grammar.signed = true,
+                _ => options.extra.push(arg),
+            }
+        }
+
+        options.grammar.validate().map_err(|e| anyhow!(e))?;
+
+        Ok(options)
+    }
+
+    /// Fails if any flag was left over for a binary that takes no flags of its own.
+    pub fn expect_no_extra(&self) -> Result<()> {
+        match self.extra.first() {
+            Some(arg) => Err(anyhow!("Unexpected argument `{arg}`")),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails on `--instructions`, `--signed` or a `--max-digits` above 4, for the binaries that
+    /// only find the puzzle's unsigned `mul`, `do` and `don't` rather than running the
+    /// interpreter. Their operands are `u16`s, and a five digit one might not fit.
+    pub fn expect_puzzle_instructions(&self) -> Result<()> {
+        if !self.instructions.is_empty() {
+            Err(anyhow!("--instructions needs the interpreter, try `run`"))
+        } else if self.grammar.signed {
+            Err(anyhow!("--signed needs the interpreter, try `run`"))
+        } else if self.grammar.max_digits > MAX_PUZZLE_DIGITS {
+            Err(anyhow!(
+                "--max-digits above {MAX_PUZZLE_DIGITS} needs the interpreter, try `run`"
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fails on `--regex`, for the binaries that never use the regexes.
+    pub fn expect_no_regex(&self) -> Result<()> {
+        match self.regex {
+            true => Err(anyhow!("--regex only applies to part1 and part2")),
+            false => Ok(()),
+        }
+    }
+
+    /// Every instruction in the input, found by whichever scanner was asked for, with operands
+    /// following the grammar's digit counts. Regex matches that don't parse are listed on stderr.
+    pub fn instructions(&self, input: &str) -> Vec<Instruction> {
+        if self.regex {
+            let mut failures = Vec::new();
+            let instructions = regex_instructions_checked(input, &self.grammar)
+                .into_iter()
+                .filter_map(|instruction| instruction.map_err(|error| failures.push(error)).ok())
+                .collect();
+
+            report_failures(&failures);
+            instructions
+        } else {
+            Lexer::with_grammar(input, self.grammar).collect()
+        }
+    }
+
+    /// The built-in instructions picked by `--instructions`, or the puzzle's own, following the
+    /// grammar from the flags.
+    pub fn instruction_set(&self) -> Result<InstructionSet> {
+        let instructions = if self.instructions.is_empty() {
+            InstructionSet::puzzle()
+        } else {
+            InstructionSet::builtin()
+                .select(&self.instructions)
+                .map_err(|e| anyhow!(e))?
+        };
+
+        Ok(instructions.with_grammar(self.grammar))
+    }
+}
+
+/// Lists the regex matches that were skipped because they don't parse on stderr.
+fn report_failures(failures: &[InstructionError]) {
+    if !failures.is_empty() {
+        eprintln!(
+            "Skipped {} instruction(s) that don't parse:",
+            failures.len()
+        );
+        failures.iter().for_each(|error| eprintln!("  {error}"));
+    }
+}
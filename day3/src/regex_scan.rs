@@ -0,0 +1,55 @@
+use regex::{Match, Regex};
+
+use crate::{Grammar, Instruction, InstructionError};
+
+/// Finds every instruction with a regex, the way day3 always has.
+///
+/// Each match is handed to [`Instruction::try_from`], and matches that don't parse (operands too
+/// big for a `u16`) are dropped, as are multiplications whose operands have more or fewer digits
+/// than the [`Grammar`] allows. [`crate::Lexer`] gives the same instructions without the regexes.
+pub fn regex_instructions(input: &str, grammar: &Grammar) -> Vec<Instruction> {
+    matches(input, grammar)
+        .into_iter()
+        .filter_map(|instruction| Instruction::try_from(instruction.as_str()).ok())
+        .collect()
+}
+
+/// Like [`regex_instructions`], but keeps the matches that don't parse as errors, with spans
+/// into `input`.
+pub fn regex_instructions_checked(
+    input: &str,
+    grammar: &Grammar,
+) -> Vec<Result<Instruction, InstructionError>> {
+    matches(input, grammar)
+        .into_iter()
+        .map(|instruction| {
+            Instruction::try_from(instruction.as_str())
+                .map_err(|error| error.offset_by(instruction.start()))
+        })
+        .collect()
+}
+
+/// Every match whose operands follow the grammar's digit counts.
+///
+/// Filtering afterwards finds the same instructions as putting the counts in the regex, since
+/// the rest of a rejected match can't hold the start of another instruction.
+fn matches<'a>(input: &'a str, grammar: &Grammar) -> Vec<Match<'a>> {
+    let regex = Regex::new(r"mul\([0-9]+,[0-9]+\)|do\(\)|don't\(\)").unwrap();
+    let digits = grammar.min_digits..=grammar.max_digits;
+
+    regex
+        .find_iter(input)
+        .filter(|instruction| {
+            let text = instruction.as_str();
+            match text
+                .strip_prefix("mul(")
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                Some(operands) => operands
+                    .split(',')
+                    .all(|operand| digits.contains(&operand.len())),
+                None => true,
+            }
+        })
+        .collect()
+}
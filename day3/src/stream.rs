@@ -0,0 +1,110 @@
+use std::io::{self, ErrorKind, Read};
+
+use crate::{lexer::Machine, Grammar, Instruction, SumOverflow};
+
+/// Finds instructions in memory read a chunk at a time, for dumps too big to hold in memory.
+///
+/// The lexer's state carries over from one chunk to the next, so an instruction split across a
+/// chunk boundary is found just as if the input were in one piece.
+pub struct ChunkedLexer<R: Read> {
+    reader: R,
+    chunk: Box<[u8]>,
+    filled: usize,
+    position: usize,
+    machine: Machine,
+}
+
+impl<R: Read> ChunkedLexer<R> {
+    pub fn new(reader: R, chunk_size: usize) -> Self {
+        Self::with_grammar(reader, chunk_size, Grammar::default())
+    }
+
+    /// Like [`crate::Lexer::with_grammar`], only the grammar's digit counts apply.
+    pub fn with_grammar(reader: R, chunk_size: usize, grammar: Grammar) -> Self {
+        Self {
+            reader,
+            chunk: vec![0; chunk_size.max(1)].into_boxed_slice(),
+            filled: 0,
+            position: 0,
+            machine: Machine::new(grammar),
+        }
+    }
+}
+
+impl<R: Read> Iterator for ChunkedLexer<R> {
+    type Item = io::Result<Instruction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some(&byte) = self.chunk[..self.filled].get(self.position) {
+                self.position += 1;
+                if let Some(instruction) = self.machine.feed(byte) {
+                    return Some(Ok(instruction));
+                }
+            }
+
+            match self.reader.read(&mut self.chunk) {
+                Ok(0) => return None,
+                Ok(filled) => {
+                    self.filled = filled;
+                    self.position = 0;
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+/// Both parts' sums, built up one instruction at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sums {
+    /// Every multiplication, for part1.
+    pub all: u64,
+    /// Only the multiplications not switched off by a `don't()`, for part2.
+    pub enabled: u64,
+    do_mul: bool,
+}
+
+impl Default for Sums {
+    fn default() -> Self {
+        Self {
+            all: 0,
+            enabled: 0,
+            do_mul: true,
+        }
+    }
+}
+
+impl Sums {
+    /// Adds an instruction, leaving the sums untouched if either would overflow.
+    pub fn add(&mut self, instruction: Instruction) -> Result<(), SumOverflow> {
+        match instruction {
+            Instruction::Do => self.do_mul = true,
+            Instruction::Dont => self.do_mul = false,
+            Instruction::Multiplication(multiplication) => {
+                let product = u64::from(multiplication.multiply());
+                let all = self.all.checked_add(product).ok_or(SumOverflow)?;
+                if self.do_mul {
+                    self.enabled = self.enabled.checked_add(product).ok_or(SumOverflow)?;
+                }
+                self.all = all;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the whole of `reader` in chunks of `chunk_size` bytes, summing both parts as it goes.
+///
+/// A sum too big for a `u64` fails with [`ErrorKind::InvalidData`], wrapping a [`SumOverflow`].
+pub fn stream_sums(reader: impl Read, chunk_size: usize, grammar: Grammar) -> io::Result<Sums> {
+    let mut sums = Sums::default();
+    for instruction in ChunkedLexer::with_grammar(reader, chunk_size, grammar) {
+        sums.add(instruction?)
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+    }
+
+    Ok(sums)
+}
@@ -0,0 +1,182 @@
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use crate::{InstructionSet, Interpreter, SumOverflow};
+
+/// One recognised instruction and what it did.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceEntry {
+    /// Where the instruction starts, in bytes from the start of the input.
+    pub offset: usize,
+    /// 1-based, with `column` counted in characters.
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub name: String,
+    pub operands: Vec<i64>,
+    /// Whether instructions were enabled when this one was reached, before it ran.
+    pub enabled: bool,
+    /// How much it added to the sum, which is 0 for anything disabled.
+    pub contribution: i64,
+    /// The running sum after it ran.
+    pub sum: i64,
+}
+
+/// Runs every instruction in the input, recording each one. Fails if the sum gets too big for an
+/// `i64`.
+pub fn trace(instructions: &InstructionSet, input: &str) -> Result<Vec<TraceEntry>, SumOverflow> {
+    let mut interpreter = Interpreter::default();
+    let mut cursor = Cursor::default();
+
+    instructions
+        .scan(input)
+        .map(|call| {
+            cursor.advance_to(input, call.span.start);
+            let enabled = interpreter.enabled;
+            let contribution = interpreter.execute(&call)?;
+
+            Ok(TraceEntry {
+                offset: call.span.start,
+                line: cursor.line,
+                column: cursor.column,
+                text: input[call.span.clone()].to_string(),
+                name: call.operation.name().to_string(),
+                operands: call.operands,
+                enabled,
+                contribution,
+                sum: interpreter.sum,
+            })
+        })
+        .collect()
+}
+
+/// Tracks the line and column of a byte offset, moving forwards through the input.
+struct Cursor {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl Cursor {
+    fn advance_to(&mut self, input: &str, offset: usize) {
+        for char in input[self.offset..offset].chars() {
+            if char == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = offset;
+    }
+}
+
+/// How the trace is printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// A listing with a row per instruction, then the sum.
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown format `{s}`, expected text or json")),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+    instructions: &'a [TraceEntry],
+    sum: i64,
+}
+
+pub fn write_trace(
+    mut writer: impl Write,
+    entries: &[TraceEntry],
+    format: TraceFormat,
+) -> io::Result<()> {
+    let sum = entries.last().map_or(0, |entry| entry.sum);
+
+    match format {
+        TraceFormat::Text => {
+            let positions: Vec<String> = entries
+                .iter()
+                .map(|entry| format!("{}:{}", entry.line, entry.column))
+                .collect();
+            let width = |header: &str, values: &mut dyn Iterator<Item = usize>| {
+                values.max().unwrap_or(0).max(header.len())
+            };
+            let offset_width = width(
+                "Offset",
+                &mut entries.iter().map(|entry| entry.offset.to_string().len()),
+            );
+            let position_width = width(
+                "Line:Col",
+                &mut positions.iter().map(|position| position.len()),
+            );
+            let text_width = width(
+                "Instruction",
+                &mut entries.iter().map(|entry| entry.text.chars().count()),
+            );
+            let contribution_width = width(
+                "Adds",
+                &mut entries
+                    .iter()
+                    .map(|entry| entry.contribution.to_string().len()),
+            );
+
+            writeln!(
+                writer,
+                "{:>offset_width$}  {:<position_width$}  State     {:<text_width$}  {:>contribution_width$}  Sum",
+                "Offset", "Line:Col", "Instruction", "Adds"
+            )?;
+
+            entries
+                .iter()
+                .zip(&positions)
+                .try_for_each(|(entry, position)| {
+                    let state = if entry.enabled { "enabled" } else { "disabled" };
+
+                    writeln!(
+                        writer,
+                        "{:>offset_width$}  {position:<position_width$}  {state:<8}  {:<text_width$}  {:>contribution_width$}  {}",
+                        entry.offset, entry.text, entry.contribution, entry.sum
+                    )
+                })?;
+
+            writeln!(writer, "Result: {sum}")
+        }
+        TraceFormat::Json => {
+            serde_json::to_writer_pretty(
+                &mut writer,
+                &Output {
+                    instructions: entries,
+                    sum,
+                },
+            )?;
+            writeln!(writer)
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use day3::{
+    regex_instructions_checked, Grammar, Instruction, InstructionError, Multiplication, Operand,
+};
+
+#[test]
+fn errors_carry_the_span_at_fault() {
+    assert_eq!(
+        Instruction::try_from("mul(12,34)"),
+        Ok(Instruction::Multiplication(Multiplication { x: 12, y: 34 }))
+    );
+    assert_eq!(
+        Instruction::try_from("mul(,34)"),
+        Err(InstructionError::MissingOperand {
+            operand: Operand::X,
+            span: 4..4
+        })
+    );
+    assert_eq!(
+        Instruction::try_from("mul(12)"),
+        Err(InstructionError::MissingOperand {
+            operand: Operand::Y,
+            span: 6..6
+        })
+    );
+    assert_eq!(
+        Instruction::try_from("mul(12,70000)"),
+        Err(InstructionError::Overflow {
+            operand: Operand::Y,
+            span: 7..12
+        })
+    );
+    assert_eq!(
+        Instruction::try_from("mul(1,2,3)"),
+        Err(InstructionError::InvalidOperand {
+            operand: Operand::Y,
+            span: 6..9
+        })
+    );
+    assert_eq!(
+        Instruction::try_from("mul(a,1)"),
+        Err(InstructionError::InvalidOperand {
+            operand: Operand::X,
+            span: 4..5
+        })
+    );
+    assert_eq!(
+        Instruction::try_from("mul(1,2,3)").unwrap_err().to_string(),
+        "bytes 6..9: operand y isn't a number"
+    );
+    assert_eq!(
+        Instruction::try_from("mul[1,2]"),
+        Err(InstructionError::UnknownInstruction { span: 0..8 })
+    );
+}
+
+#[test]
+fn checked_regex_scan_keeps_failures() {
+    let memory = "xmul(2,4)mul(99999,1)don't()";
+    let grammar = Grammar {
+        max_digits: 5,
+        ..Grammar::default()
+    };
+
+    assert_eq!(
+        regex_instructions_checked(memory, &grammar),
+        [
+            Ok(Instruction::Multiplication(Multiplication { x: 2, y: 4 })),
+            Err(InstructionError::Overflow {
+                operand: Operand::X,
+                span: 13..18
+            }),
+            Ok(Instruction::Dont),
+        ]
+    );
+    assert_eq!(
+        regex_instructions_checked(memory, &grammar)[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string(),
+        "bytes 13..18: operand x doesn't fit in a u16"
+    );
+}
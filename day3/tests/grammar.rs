@@ -0,0 +1,102 @@
+use day3::{Grammar, InstructionSet, Rejection};
+
+/// The reason for every near miss in the memory, with the text it covers.
+fn near_misses(instructions: &InstructionSet, memory: &str) -> Vec<(String, Rejection)> {
+    instructions
+        .scan_with_near_misses(memory)
+        .filter_map(Result::err)
+        .map(|near_miss| (memory[near_miss.span].to_string(), near_miss.reason))
+        .collect()
+}
+
+#[test]
+fn near_misses_under_the_puzzle_grammar() {
+    let memory = "mul(1234,5)mul( 2,3)mul(2,3]mul(-4,2)mul(2)do(x)don't()mul(4,5)mul(12,4";
+
+    assert_eq!(
+        near_misses(&InstructionSet::puzzle(), memory),
+        [
+            (
+                "mul(1234".to_string(),
+                Rejection::TooManyDigits {
+                    operand: 1,
+                    digits: 4,
+                    max: 3
+                }
+            ),
+            (
+                "mul( ".to_string(),
+                Rejection::Expected {
+                    expected: "a digit",
+                    found: Some(' ')
+                }
+            ),
+            (
+                "mul(2,3]".to_string(),
+                Rejection::Expected {
+                    expected: "`)`",
+                    found: Some(']')
+                }
+            ),
+            ("mul(-".to_string(), Rejection::Signed { operand: 1 }),
+            (
+                "mul(2)".to_string(),
+                Rejection::Expected {
+                    expected: "`,`",
+                    found: Some(')')
+                }
+            ),
+            (
+                "do(x".to_string(),
+                Rejection::Expected {
+                    expected: "`)`",
+                    found: Some('x')
+                }
+            ),
+            (
+                "mul(12,4".to_string(),
+                Rejection::Expected {
+                    expected: "`)`",
+                    found: None
+                }
+            ),
+        ]
+    );
+    assert_eq!(InstructionSet::puzzle().run(memory).unwrap(), 0);
+}
+
+#[test]
+fn configurable_operands() {
+    let memory = "mul(1234,5)mul(-4,2)mul(7,08)";
+
+    let signed = InstructionSet::puzzle().with_grammar(Grammar {
+        signed: true,
+        ..Grammar::default()
+    });
+    assert_eq!(signed.run(memory).unwrap(), -8 + 56);
+
+    let wide = InstructionSet::puzzle().with_grammar(Grammar {
+        min_digits: 2,
+        max_digits: 4,
+        signed: false,
+    });
+    assert_eq!(wide.run(memory).unwrap(), 0);
+    assert_eq!(
+        near_misses(&wide, memory)[0].1,
+        Rejection::TooFewDigits {
+            operand: 2,
+            digits: 1,
+            min: 2
+        }
+    );
+
+    let huge = InstructionSet::puzzle().with_grammar(Grammar {
+        min_digits: 1,
+        max_digits: 30,
+        signed: false,
+    });
+    assert_eq!(
+        near_misses(&huge, "mul(99999999999999999999,1)")[0].1,
+        Rejection::Overflow { operand: 1 }
+    );
+}
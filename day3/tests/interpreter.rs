@@ -0,0 +1,164 @@
+mod common;
+
+use day3::{Effect, Grammar, InstructionSet, Interpreter, Operation, SumOverflow};
+use regex::Regex;
+
+use common::XorShift;
+
+/// The puzzle's instructions found with a regex instead, as part2 would with the puzzle's own
+/// one-to-three-digit operands.
+fn sum_with_regex(regex: &Regex, memory: &str) -> i64 {
+    let mut enabled = true;
+    let mut sum = 0;
+    for captures in regex.captures_iter(memory) {
+        match &captures[0] {
+            "do()" => enabled = true,
+            "don't()" => enabled = false,
+            _ if enabled => {
+                sum += captures[1].parse::<i64>().unwrap() * captures[2].parse::<i64>().unwrap()
+            }
+            _ => {}
+        }
+    }
+
+    sum
+}
+
+#[test]
+fn puzzle_instructions_match_a_regex() {
+    let pieces: Vec<&str> = "m u l ( ) , d o n ' t mul( do( don't( add( neg( 1 23 999 65536 _ x"
+        .split_whitespace()
+        .collect();
+
+    let regex = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|do\(\)|don't\(\)").unwrap();
+    let mut rng = XorShift(0x2024_0021);
+
+    for _ in 0..20_000 {
+        let memory: String = (0..rng.below(40))
+            .map(|_| pieces[rng.below(pieces.len() as u64) as usize])
+            .collect();
+
+        assert_eq!(
+            InstructionSet::puzzle().run(&memory).unwrap(),
+            sum_with_regex(&regex, &memory),
+            "{memory:?}"
+        );
+    }
+}
+
+#[test]
+fn extended_instructions() {
+    let memory = "add(2,3)sub(1,5)neg(7)div(9,2)div(1,0)mod(10,4)mul(2,2)don't()add(1,1)do()";
+
+    assert_eq!(InstructionSet::builtin().run(memory).unwrap(), 4);
+    assert_eq!(
+        InstructionSet::builtin()
+            .select(&["add", "sub"])
+            .unwrap()
+            .run(memory)
+            .unwrap(),
+        3
+    );
+    assert!(InstructionSet::builtin().select(&["pow"]).is_err());
+}
+
+struct Max;
+
+impl Operation for Max {
+    fn name(&self) -> &str {
+        "max"
+    }
+
+    fn arity(&self) -> usize {
+        3
+    }
+
+    fn run(&self, operands: &[i64]) -> Effect {
+        Effect::Contribute(operands.iter().copied().max().unwrap())
+    }
+}
+
+#[test]
+fn registered_instructions() {
+    let mut instructions = InstructionSet::puzzle();
+    instructions.register(Max);
+
+    assert_eq!(instructions.run("max(1,9,4)mul(2,3)max(1,2)").unwrap(), 15);
+}
+
+/// Adds its operands, which may be negative and up to five digits long.
+struct SignedAdd;
+
+impl Operation for SignedAdd {
+    fn name(&self) -> &str {
+        "sadd"
+    }
+
+    fn arity(&self) -> usize {
+        2
+    }
+
+    fn grammar(&self) -> Option<Grammar> {
+        Some(Grammar {
+            max_digits: 5,
+            signed: true,
+            ..Grammar::default()
+        })
+    }
+
+    fn run(&self, operands: &[i64]) -> Effect {
+        Effect::Contribute(operands[0] + operands[1])
+    }
+}
+
+#[test]
+fn instructions_with_their_own_grammar() {
+    let mut instructions = InstructionSet::puzzle();
+    instructions.register(SignedAdd);
+
+    // `mul` keeps the puzzle's grammar, so its signed and four-digit calls are skipped.
+    assert_eq!(
+        instructions
+            .run("sadd(-10000,3)mul(-2,3)sadd(1234,-1)mul(1234,2)mul(2,3)")
+            .unwrap(),
+        -10000 + 3 + 1234 - 1 + 6
+    );
+
+    let near_misses: Vec<String> = instructions
+        .scan_with_near_misses("mul(-2,3)sadd(123456,1)")
+        .filter_map(Result::err)
+        .map(|near_miss| near_miss.reason.to_string())
+        .collect();
+    assert_eq!(
+        near_misses,
+        [
+            "operand 1 has a sign, but operands are unsigned",
+            "operand 1 has 6 digits, at most 5 allowed"
+        ]
+    );
+}
+
+#[test]
+fn sums_too_big_for_an_i64_are_an_error() {
+    let instructions = InstructionSet::builtin().with_grammar(Grammar {
+        max_digits: 19,
+        ..Grammar::default()
+    });
+
+    assert_eq!(
+        instructions.run("add(9000000000000000000,0)add(9000000000000000000,0)add(1,2)"),
+        Err(SumOverflow)
+    );
+
+    // The call that would overflow leaves the sum as it was.
+    let calls: Vec<_> = instructions
+        .scan("add(9000000000000000000,0)add(9000000000000000000,0)")
+        .collect();
+    let mut interpreter = Interpreter::default();
+    assert_eq!(
+        interpreter.execute(&calls[0]),
+        Ok(9_000_000_000_000_000_000)
+    );
+    assert_eq!(interpreter.execute(&calls[1]), Err(SumOverflow));
+    assert_eq!(interpreter.sum, 9_000_000_000_000_000_000);
+}
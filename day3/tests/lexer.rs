@@ -0,0 +1,96 @@
+mod common;
+
+use day3::{regex_instructions, sum_all, sum_enabled, Grammar, InstructionSet, Lexer};
+
+use common::XorShift;
+
+#[test]
+fn examples() {
+    let part1 = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    let part2 = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    assert_eq!(sum_all(Lexer::new(part1)).unwrap(), 161);
+    assert_eq!(sum_enabled(Lexer::new(part2)).unwrap(), 48);
+}
+
+#[test]
+fn matches_regex_on_random_memory() {
+    // Pieces of instructions, so the generated memory is full of near misses and restarts.
+    let pieces: Vec<&str> = "m u l ( ) , d o n ' t mul( do( don't( mumul( 1 23 999 65535 65536 \
+        1000000000000 _ x é"
+        .split_whitespace()
+        .collect();
+
+    let mut rng = XorShift(0x2024_0020);
+
+    for _ in 0..2_000 {
+        let memory: String = (0..rng.below(40))
+            .map(|_| pieces[rng.below(pieces.len() as u64) as usize])
+            .collect();
+        let grammar = random_grammar(&mut rng, 14);
+
+        assert_eq!(
+            Lexer::with_grammar(&memory, grammar).collect::<Vec<_>>(),
+            regex_instructions(&memory, &grammar),
+            "{memory:?} {grammar:?}"
+        );
+    }
+}
+
+fn random_grammar(rng: &mut XorShift, max_digits: u64) -> Grammar {
+    let min_digits = 1 + rng.below(3) as usize;
+    Grammar {
+        min_digits,
+        max_digits: min_digits + rng.below(max_digits - 2) as usize,
+        signed: false,
+    }
+}
+
+#[test]
+fn digit_counts_follow_the_grammar() {
+    let memory = "mul(1234,5)mul(2,3)";
+    let interpreter = InstructionSet::puzzle().run(memory).unwrap();
+
+    assert_eq!(interpreter, 6);
+    assert_eq!(sum_all(Lexer::new(memory)).unwrap(), 6);
+    assert_eq!(sum_enabled(Lexer::new(memory)).unwrap(), 6);
+    assert_eq!(
+        sum_enabled(regex_instructions(memory, &Grammar::default())).unwrap(),
+        6
+    );
+
+    let grammar = Grammar {
+        min_digits: 2,
+        max_digits: 4,
+        signed: false,
+    };
+    assert_eq!(sum_all(Lexer::with_grammar(memory, grammar)).unwrap(), 0);
+    assert_eq!(
+        sum_all(Lexer::with_grammar("mul(1234,56)", grammar)).unwrap(),
+        69_104
+    );
+}
+
+#[test]
+fn matches_the_interpreter_on_random_memory() {
+    let pieces: Vec<&str> = "m u l ( ) , d o n ' t mul( do( don't( 1 23 999 4567 _ x"
+        .split_whitespace()
+        .collect();
+
+    let mut rng = XorShift(0x2024_0022);
+
+    for _ in 0..2_000 {
+        let memory: String = (0..rng.below(40))
+            .map(|_| pieces[rng.below(pieces.len() as u64) as usize])
+            .collect();
+        // Up to four digits, so every operand fits in a `u16` and the lexer drops none of them.
+        let grammar = random_grammar(&mut rng, 4);
+        let instructions = InstructionSet::puzzle().with_grammar(grammar);
+
+        assert_eq!(
+            sum_enabled(Lexer::with_grammar(&memory, grammar)).unwrap() as i64,
+            instructions.run(&memory).unwrap(),
+            "{memory:?} {grammar:?}"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use day3::Options;
+
+fn options(args: &[&str]) -> Options {
+    Options::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+}
+
+#[test]
+fn flags_a_binary_ignores_are_rejected() {
+    assert!(options(&["--max-digits", "4"])
+        .expect_puzzle_instructions()
+        .is_ok());
+    // `mul(100000,2)` would otherwise be dropped without a word.
+    assert!(options(&["--max-digits", "6"])
+        .expect_puzzle_instructions()
+        .is_err());
+    assert!(options(&["--instructions", "add"])
+        .expect_puzzle_instructions()
+        .is_err());
+    assert!(options(&["--signed"]).expect_puzzle_instructions().is_err());
+
+    assert!(options(&["--signed"]).expect_no_regex().is_ok());
+    assert!(options(&["--regex"]).expect_no_regex().is_err());
+}
+
+#[test]
+fn digit_counts_reach_the_lexer_and_the_regexes() {
+    let memory = "mul(1234,5)mul(2,3)";
+
+    for regex in [&[][..], &["--regex"]] {
+        let found = |extra: &[&str]| {
+            let args: Vec<&str> = regex.iter().chain(extra).copied().collect();
+            options(&args).instructions(memory).len()
+        };
+
+        assert_eq!(found(&[]), 1);
+        assert_eq!(found(&["--max-digits", "4"]), 2);
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Read;
+
+use day3::{
+    stream_sums, sum_all, sum_enabled, ChunkedLexer, Grammar, Instruction, Lexer, Multiplication,
+    SumOverflow, Sums,
+};
+
+const MEMORY: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n\
+    mumul(123,45)dodon't()mul(7,7)do()é mul(65535,2)mul(65536,1)don'do()mul(9,9)";
+
+#[test]
+fn every_split_finds_the_same_instructions() {
+    let expected: Vec<_> = Lexer::new(MEMORY).collect();
+    let bytes = MEMORY.as_bytes();
+
+    for split in 0..=bytes.len() {
+        // `chain` hands back at most the rest of the first part from a read, so the lexer sees a
+        // chunk boundary exactly at `split`.
+        let reader = bytes[..split].chain(&bytes[split..]);
+        let found: Vec<_> = ChunkedLexer::new(reader, bytes.len())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(found, expected, "split at {split}");
+    }
+}
+
+#[test]
+fn every_chunk_size_gives_the_same_sums() {
+    // Five digits lets in the `u16` boundary cases.
+    let grammar = Grammar {
+        max_digits: 5,
+        ..Grammar::default()
+    };
+
+    for chunk_size in 1..=MEMORY.len() + 1 {
+        let sums = stream_sums(MEMORY.as_bytes(), chunk_size, grammar).unwrap();
+
+        assert_eq!(
+            sums.all,
+            sum_all(Lexer::with_grammar(MEMORY, grammar)).unwrap(),
+            "chunk size {chunk_size}"
+        );
+        assert_eq!(
+            sums.enabled,
+            sum_enabled(Lexer::with_grammar(MEMORY, grammar)).unwrap(),
+            "chunk size {chunk_size}"
+        );
+    }
+}
+
+#[test]
+fn sums_past_a_u32_and_up_to_a_u64() {
+    let grammar = Grammar {
+        max_digits: 5,
+        ..Grammar::default()
+    };
+    let memory = "mul(65535,65535)mul(65535,65535)";
+    let sums = stream_sums(memory.as_bytes(), 7, grammar).unwrap();
+    assert_eq!(sums.all, 2 * 65535 * 65535);
+    assert_eq!(
+        sum_all(Lexer::with_grammar(memory, grammar)),
+        Ok(2 * 65535 * 65535)
+    );
+    assert_eq!(
+        sum_enabled(Lexer::with_grammar(memory, grammar)),
+        Ok(2 * 65535 * 65535)
+    );
+
+    let biggest = Instruction::Multiplication(Multiplication {
+        x: u16::MAX,
+        y: u16::MAX,
+    });
+    let mut sums = Sums::default();
+    sums.add(biggest).unwrap();
+    sums.all = u64::MAX - 1;
+
+    assert_eq!(sums.add(biggest), Err(SumOverflow));
+    assert_eq!(sums.all, u64::MAX - 1);
+    assert_eq!(sums.enabled, 65535 * 65535);
+}
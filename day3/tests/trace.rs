@@ -0,0 +1,33 @@
+use day3::{trace, InstructionSet};
+
+#[test]
+fn positions_states_and_running_sum() {
+    let memory = "xmul(2,4)\né don't()mul(5,5)\n\ndo()mul(8,5)";
+
+    let entries = trace(&InstructionSet::puzzle(), memory).unwrap();
+    let summary: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.text.as_str(),
+                entry.offset,
+                entry.line,
+                entry.column,
+                entry.enabled,
+                entry.contribution,
+                entry.sum,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            ("mul(2,4)", 1, 1, 2, true, 8, 8),
+            ("don't()", 13, 2, 3, true, 0, 8),
+            ("mul(5,5)", 20, 2, 10, false, 0, 8),
+            ("do()", 30, 4, 1, false, 0, 8),
+            ("mul(8,5)", 34, 4, 5, true, 40, 48),
+        ]
+    );
+}